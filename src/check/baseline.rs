@@ -0,0 +1,218 @@
+//! Baseline of known findings.
+//!
+//! A baseline records the findings of a project at a point in time, so `scopelint check` only
+//! fails on findings that are not in the baseline. This makes it possible to adopt scopelint on a
+//! codebase with many pre-existing findings and fix them incrementally.
+//!
+//! Findings are matched by file, rule and a fingerprint of the offending text and source line, so
+//! unrelated changes that move a finding to another line do not invalidate the baseline.
+//!
+//! Example baseline file:
+//! ```json
+//! {
+//!   "version": 1,
+//!   "findings": [
+//!     {
+//!       "file": "./src/Counter.sol",
+//!       "rule": "src",
+//!       "fingerprint": "8d1a0f6e3c2b7a41",
+//!       "message": "internalHasNoLeadingUnderscore"
+//!     }
+//!   ]
+//! }
+//! ```
+
+use crate::{
+    check::utils::{fnv1a_hash, InvalidItem},
+    error::{Error, Result},
+};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path};
+
+/// The version of the baseline file format written by [`Baseline::write`].
+const BASELINE_VERSION: u32 = 1;
+
+/// A set of findings that do not cause `scopelint check` to fail.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The version of the baseline file format.
+    version: u32,
+    /// The findings in the baseline. The same entry may appear multiple times if a file contains
+    /// several identical findings.
+    findings: Vec<BaselineEntry>,
+}
+
+/// A single finding recorded in a [`Baseline`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// The file the finding is in.
+    pub file: String,
+    /// The name of the rule that reported the finding.
+    pub rule: String,
+    /// A fingerprint of the offending text and source line, see [`fingerprint`].
+    pub fingerprint: String,
+    /// Details about the finding. This is only informational and not used for matching.
+    pub message: String,
+}
+
+impl From<&InvalidItem> for BaselineEntry {
+    fn from(item: &InvalidItem) -> Self {
+        Self {
+            file: item.file.clone(),
+            rule: item.kind.name().to_string(),
+            fingerprint: fingerprint(item),
+            message: item.text.clone(),
+        }
+    }
+}
+
+impl BaselineEntry {
+    /// Returns `true` if the entry records the given invalid item.
+    fn matches(&self, item: &InvalidItem) -> bool {
+        self.file == item.file &&
+            self.rule == item.kind.name() &&
+            self.fingerprint == fingerprint(item)
+    }
+}
+
+impl Baseline {
+    /// Creates a baseline containing every invalid item that is reported, i.e. that is not
+    /// disabled or ignored.
+    #[must_use]
+    pub fn from_items(items: &[InvalidItem]) -> Self {
+        let findings = items
+            .iter()
+            .filter(|item| !item.is_suppressed())
+            .map(BaselineEntry::from)
+            .sorted_unstable()
+            .collect();
+        Self { version: BASELINE_VERSION, findings }
+    }
+
+    /// Loads a baseline from the given file.
+    /// # Errors
+    /// Returns an error if the file cannot be read or is not a valid baseline.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|err| {
+            Error::io(format!("Failed to read baseline {}", path.display()), &err)
+        })?;
+        let baseline: Self = serde_json::from_str(&content)
+            .map_err(|err| Error::Config(format!("Invalid baseline {}: {err}", path.display())))?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(Error::Config(format!(
+                "Unsupported baseline version {} in {}, expected {BASELINE_VERSION}",
+                baseline.version,
+                path.display()
+            )));
+        }
+        Ok(baseline)
+    }
+
+    /// Writes the baseline to the given file.
+    /// # Errors
+    /// Returns an error if the file cannot be written.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let write =
+            || -> io::Result<()> { fs::write(path, serde_json::to_string_pretty(self)? + "\n") };
+        write()
+            .map_err(|err| Error::io(format!("Failed to write baseline {}", path.display()), &err))
+    }
+
+    /// Returns the number of findings in the baseline.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    /// Returns `true` if the baseline contains no findings.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Marks every reported invalid item that is recorded in the baseline as baselined. Each
+    /// baseline entry matches at most one item. Returns the entries that did not match any item,
+    /// i.e. findings that have been fixed since the baseline was written.
+    #[must_use]
+    pub fn apply(&self, items: &mut [InvalidItem]) -> Vec<BaselineEntry> {
+        let mut remaining: Vec<&BaselineEntry> = self.findings.iter().collect();
+        for item in items.iter_mut().filter(|item| !item.is_suppressed()) {
+            if let Some(index) = remaining.iter().position(|entry| entry.matches(item)) {
+                remaining.swap_remove(index);
+                item.is_baselined = true;
+            }
+        }
+        remaining.into_iter().cloned().sorted_unstable().collect()
+    }
+}
+
+/// Returns a fingerprint of the invalid item that does not depend on its position.
+///
+/// The fingerprint is stable when code around the item changes. It is a 64-bit FNV-1a hash, in hex,
+/// of the item's text and the whitespace-normalized source line it starts on.
+#[must_use]
+pub fn fingerprint(item: &InvalidItem) -> String {
+    let source_line = item.source_line.split_whitespace().join(" ");
+    let hash = fnv1a_hash(item.text.bytes().chain([0]).chain(source_line.bytes()));
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{parse_source, rules::Rule};
+    use solang_parser::pt::Loc;
+
+    const CONTENT: &str =
+        "contract A {\n  function foo() internal {}\n  function bar() internal {}\n}";
+
+    /// Returns an invalid item for the `foo` and `bar` functions in the given source code.
+    fn invalid_items(content: &str) -> Vec<InvalidItem> {
+        let parsed = parse_source(Path::new("./src/A.sol"), content).unwrap();
+        ["foo", "bar"]
+            .iter()
+            .filter_map(|name| {
+                let start = content.find(&format!("function {name}("))? + "function ".len();
+                let loc = Loc::File(0, start, start + name.len());
+                Some(InvalidItem::new(
+                    Rule::InternalFunctionName,
+                    &parsed,
+                    loc,
+                    (*name).to_string(),
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_position() {
+        let before = invalid_items(CONTENT);
+        let after = invalid_items(
+            "contract A {\n  uint256 x;\n\n    function foo()   internal {}\n  function bar() internal {}\n}",
+        );
+
+        assert_ne!(before[0].line, after[0].line);
+        assert_eq!(fingerprint(&before[0]), fingerprint(&after[0]));
+        assert_ne!(fingerprint(&before[0]), fingerprint(&before[1]));
+    }
+
+    #[test]
+    fn test_apply_baseline() {
+        let baseline = Baseline::from_items(&invalid_items(CONTENT)[..1]);
+        assert_eq!(baseline.len(), 1);
+
+        // `foo` is in the baseline, `bar` is new.
+        let mut items = invalid_items(CONTENT);
+        assert!(baseline.apply(&mut items).is_empty());
+        assert!(items[0].is_baselined);
+        assert!(!items[1].is_baselined);
+
+        // `foo` was fixed.
+        let mut items = invalid_items(&CONTENT.replace("function foo", "function _foo"));
+        let fixed = baseline.apply(&mut items);
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(fixed[0].message, "foo");
+        assert!(items.iter().all(|item| !item.is_baselined));
+    }
+}
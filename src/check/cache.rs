@@ -0,0 +1,180 @@
+//! Cache of the findings of each file between runs.
+//!
+//! `scopelint check` stores the findings of every file it validates in `.scopelint-cache/`, keyed
+//! by a hash of the file's content. On the next run, files whose content did not change reuse
+//! their cached findings instead of being parsed and validated again.
+//!
+//! Findings depend on the scopelint version and on the configuration, so the whole cache is
+//! discarded when either changes. The configuration is hashed from the contents of the
+//! `.scopelint` and `foundry.toml` files that are in effect, so editing either of them invalidates
+//! the cache automatically.
+
+use crate::{
+    check::{
+        file_config::FileConfig,
+        utils::{fnv1a_hash, InvalidItem},
+    },
+    error::{Error, Result},
+    foundry_config::CheckPaths,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The directory the cache is stored in, relative to the project root.
+pub const CACHE_DIR: &str = ".scopelint-cache";
+
+/// The file in [`CACHE_DIR`] that holds the cached findings.
+const CACHE_FILE: &str = "findings.json";
+
+/// The findings of previously validated files.
+#[derive(Serialize, Deserialize)]
+pub struct Cache {
+    /// The scopelint version that wrote the cache.
+    version: String,
+    /// A hash of the configuration the cached findings were computed with, see [`config_hash`].
+    config_hash: String,
+    /// The cached findings, by file.
+    files: BTreeMap<String, CacheEntry>,
+}
+
+/// The cached findings of a single file.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// A hash of the file's content, see [`content_hash`].
+    content_hash: String,
+    /// The findings of the file, before any baseline or `--changed-since` filtering.
+    items: Vec<InvalidItem>,
+}
+
+impl Cache {
+    /// Creates an empty cache for the current scopelint version and the configuration of the
+    /// project at `root`, see [`config_hash`].
+    #[must_use]
+    pub fn new(root: &Path, config_file: Option<&Path>) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            config_hash: config_hash(root, config_file),
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Loads the cache of the project at `root`. Returns an empty cache if there is none, it
+    /// cannot be read, or it was written by another scopelint version or for another
+    /// configuration.
+    #[must_use]
+    pub fn load(root: &Path, config_file: Option<&Path>) -> Self {
+        let current = Self::new(root, config_file);
+        let Ok(content) = fs::read_to_string(root.join(CACHE_DIR).join(CACHE_FILE)) else {
+            return current;
+        };
+        match serde_json::from_str::<Self>(&content) {
+            Ok(cache)
+                if cache.version == current.version && cache.config_hash == current.config_hash =>
+            {
+                cache
+            }
+            _ => current,
+        }
+    }
+
+    /// Writes the cache to [`CACHE_DIR`] in the project at `root`, creating it if needed. The
+    /// directory contains a `.gitignore` so the cache is never committed.
+    /// # Errors
+    /// Returns an error if the directory or the cache file cannot be written.
+    pub fn write(&self, root: &Path) -> Result<()> {
+        let dir = root.join(CACHE_DIR);
+        let write = || -> io::Result<()> {
+            fs::create_dir_all(&dir)?;
+            fs::write(dir.join(".gitignore"), "*\n")?;
+            fs::write(dir.join(CACHE_FILE), serde_json::to_string(self)?)?;
+            Ok(())
+        };
+        write().map_err(|err| Error::io(format!("Failed to write cache {}", dir.display()), &err))
+    }
+
+    /// Returns the cached findings of the file, if its content has not changed since they were
+    /// cached.
+    #[must_use]
+    pub fn get(&self, file: &Path, content_hash: &str) -> Option<&[InvalidItem]> {
+        self.files
+            .get(&file.display().to_string())
+            .filter(|entry| entry.content_hash == content_hash)
+            .map(|entry| entry.items.as_slice())
+    }
+
+    /// Caches the findings of the file with the given content hash.
+    pub fn insert(&mut self, file: &Path, content_hash: String, items: Vec<InvalidItem>) {
+        self.files.insert(file.display().to_string(), CacheEntry { content_hash, items });
+    }
+
+    /// Removes the entries of all files that are not in `files`, e.g. because they were deleted.
+    pub fn retain(&mut self, files: &[PathBuf]) {
+        let files: HashSet<String> = files.iter().map(|file| file.display().to_string()).collect();
+        self.files.retain(|file, _| files.contains(file));
+    }
+}
+
+/// Returns a hash of the content of a file.
+#[must_use]
+pub fn content_hash(src: &str) -> String {
+    format!("{:016x}", fnv1a_hash(src.bytes()))
+}
+
+/// Returns a hash of the `.scopelint` and `foundry.toml` files in effect for the project at
+/// `root`. Missing files hash the same as empty ones.
+///
+/// The `.scopelint` file is `config_file` if given, otherwise the one found from `root`.
+#[must_use]
+pub fn config_hash(root: &Path, config_file: Option<&Path>) -> String {
+    let read = |path: Option<PathBuf>| {
+        path.and_then(|path| fs::read_to_string(path).ok()).unwrap_or_default()
+    };
+    let file_config =
+        read(config_file.map_or_else(
+            || FileConfig::find_config_file_from(root),
+            |file| Some(file.to_path_buf()),
+        ));
+    let foundry_config = read(CheckPaths::find_foundry_toml_from(root));
+    let hash = fnv1a_hash(file_config.bytes().chain([0]).chain(foundry_config.bytes()));
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{parse_source, rules::Rule, TempDir};
+    use solang_parser::pt::Loc;
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let temp_dir = TempDir::new("cache-test");
+        let dir = temp_dir.path();
+        let file = Path::new("./src/Counter.sol");
+        let src = "contract Counter {}";
+        let parsed = parse_source(file, src).unwrap();
+        let item = InvalidItem::new(Rule::SpdxHeader, &parsed, Loc::File(0, 0, 0), String::new());
+
+        let mut cache = Cache::new(dir, None);
+        cache.insert(file, content_hash(src), vec![item.clone()]);
+        cache.insert(Path::new("./src/Deleted.sol"), content_hash(""), Vec::new());
+        cache.retain(&[file.to_path_buf()]);
+        cache.write(dir).unwrap();
+
+        let cache = Cache::load(dir, None);
+        assert!(cache.get(file, &content_hash(src)) == Some([item].as_slice()));
+        assert!(cache.get(file, &content_hash("contract Changed {}")).is_none());
+        assert!(cache.get(Path::new("./src/Deleted.sol"), &content_hash("")).is_none());
+        assert_eq!(fs::read_to_string(dir.join(CACHE_DIR).join(".gitignore")).unwrap(), "*\n");
+
+        // A cache written by another version is discarded.
+        let cache_file = dir.join(CACHE_DIR).join(CACHE_FILE);
+        let content = fs::read_to_string(&cache_file).unwrap();
+        let content = content.replace(env!("CARGO_PKG_VERSION"), "0.0.0-other");
+        fs::write(&cache_file, content).unwrap();
+        assert!(Cache::load(dir, None).get(file, &content_hash(src)).is_none());
+    }
+}
@@ -0,0 +1,212 @@
+//! Lines changed relative to a git ref, used to only report findings on changed code.
+
+use crate::error::{Error, Result};
+use std::{collections::HashMap, ops::RangeInclusive, path::Path, process};
+
+/// The lines of each file that changed relative to a git ref, as reported by `git diff`. Paths
+/// are relative to the directory git was run in, without a leading `./`.
+#[derive(Debug, Default)]
+pub struct ChangedLines {
+    /// Changed files and the ranges of added or modified lines in them.
+    files: HashMap<String, Vec<RangeInclusive<usize>>>,
+}
+
+impl ChangedLines {
+    /// Runs `git diff` against `git_ref` in the directory `dir` and collects the changed lines of
+    /// the files in it. Untracked files are considered changed in their entirety.
+    ///
+    /// The `a/` and `b/` prefixes are passed explicitly, since `diff.noprefix` or
+    /// `diff.mnemonicPrefix` in the user's git config would change them.
+    /// # Errors
+    /// Returns an error if `git` cannot be run or fails, e.g. because the ref does not exist.
+    pub fn from_git(dir: &Path, git_ref: &str) -> Result<Self> {
+        let diff = run_git(
+            dir,
+            &[
+                "diff",
+                "--relative",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--src-prefix=a/",
+                "--dst-prefix=b/",
+                git_ref,
+                "--",
+                ".",
+            ],
+        )?;
+        let mut changed_lines = Self::from_diff(&diff);
+
+        // With `-z`, paths are separated by NUL and never quoted.
+        let untracked = run_git(dir, &["ls-files", "-z", "--others", "--exclude-standard"])?;
+        for file in untracked.split('\0').filter(|file| !file.is_empty()) {
+            changed_lines.files.insert(file.to_string(), vec![1..=usize::MAX]);
+        }
+        Ok(changed_lines)
+    }
+
+    /// Parses the output of `git diff --unified=0` with the default `b/` prefix for new files. Only
+    /// the new side of each hunk is kept, so deleted lines and deleted files are ignored.
+    #[must_use]
+    pub fn from_diff(diff: &str) -> Self {
+        let mut files: HashMap<String, Vec<RangeInclusive<usize>>> = HashMap::new();
+        let mut current_file = None;
+
+        for line in diff.lines() {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                current_file = unquote(path).strip_prefix("b/").map(str::to_string);
+                if let Some(file) = &current_file {
+                    files.entry(file.clone()).or_default();
+                }
+            } else if let (Some(file), Some(hunk)) = (&current_file, line.strip_prefix("@@ ")) {
+                if let Some(range) = parse_hunk_header(hunk) {
+                    files.entry(file.clone()).or_default().push(range);
+                }
+            }
+        }
+        Self { files }
+    }
+
+    /// Returns `true` if the file changed.
+    #[must_use]
+    pub fn contains_file(&self, file: &str) -> bool {
+        self.files.contains_key(normalize(file))
+    }
+
+    /// Returns `true` if the given line of the file was added or modified.
+    #[must_use]
+    pub fn contains_line(&self, file: &str, line: usize) -> bool {
+        self.files
+            .get(normalize(file))
+            .is_some_and(|ranges| ranges.iter().any(|range| range.contains(&line)))
+    }
+}
+
+/// Strips the leading `./` used for file names in findings.
+fn normalize(file: &str) -> &str {
+    file.strip_prefix("./").unwrap_or(file)
+}
+
+/// Unquotes a path that git quoted because it contains special or non-ASCII characters, e.g.
+/// `"b/src/\303\251.sol"`. Other paths are returned as is.
+fn unquote(path: &str) -> String {
+    let Some(quoted) = path.strip_prefix('"').and_then(|path| path.strip_suffix('"')) else {
+        return path.to_string();
+    };
+
+    let mut bytes = Vec::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('a') => bytes.push(0x07),
+            Some('b') => bytes.push(0x08),
+            Some('f') => bytes.push(0x0c),
+            Some('n') => bytes.push(b'\n'),
+            Some('r') => bytes.push(b'\r'),
+            Some('t') => bytes.push(b'\t'),
+            Some('v') => bytes.push(0x0b),
+            // Other bytes are escaped as three octal digits.
+            Some(digit @ '0'..='3') => {
+                let octal: String = std::iter::once(digit).chain(chars.by_ref().take(2)).collect();
+                bytes.extend(u8::from_str_radix(&octal, 8));
+            }
+            Some(c) => bytes.push(u8::try_from(c).unwrap_or(b'?')),
+            None => {}
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Parses the new-file range of a hunk header such as `-12,3 +14,2 @@ function foo()`, returning
+/// `None` if the hunk only removes lines.
+fn parse_hunk_header(hunk: &str) -> Option<RangeInclusive<usize>> {
+    let new_range = hunk.split_whitespace().find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = match new_range.split_once(',') {
+        Some((start, count)) => (start.parse::<usize>().ok()?, count.parse::<usize>().ok()?),
+        None => (new_range.parse::<usize>().ok()?, 1),
+    };
+    (count > 0).then(|| start..=start + count - 1)
+}
+
+/// Runs git with the given arguments in the directory `dir` and returns its stdout.
+fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|err| Error::ExternalTool(format!("Failed to run git: {err}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::ExternalTool(format!("git {} failed: {}", args[0], stderr.trim())));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|err| Error::ExternalTool(format!("git {} output is not UTF-8: {err}", args[0])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hunk_header() {
+        assert_eq!(parse_hunk_header("-12,3 +14,2 @@ function foo()"), Some(14..=15));
+        assert_eq!(parse_hunk_header("-12 +14 @@"), Some(14..=14));
+        assert_eq!(parse_hunk_header("-12,3 +11,0 @@"), None);
+    }
+
+    #[test]
+    fn test_from_diff() {
+        let diff = "\
+diff --git a/src/Counter.sol b/src/Counter.sol
+index 1111111..2222222 100644
+--- a/src/Counter.sol
++++ b/src/Counter.sol
+@@ -5,0 +6,2 @@ contract Counter {
++  uint256 public x;
++  uint256 public y;
+@@ -20 +22 @@ contract Counter {
+-  function foo() public {}
++  function bar() public {}
+@@ -30,2 +31,0 @@ contract Counter {
+-  // removed
+-  // lines
+diff --git a/src/Removed.sol b/src/Removed.sol
+deleted file mode 100644
+--- a/src/Removed.sol
++++ /dev/null
+@@ -1,2 +0,0 @@
+-// SPDX-License-Identifier: MIT
+-pragma solidity ^0.8.17;
+";
+        let changed_lines = ChangedLines::from_diff(diff);
+
+        assert!(changed_lines.contains_file("./src/Counter.sol"));
+        assert!(!changed_lines.contains_file("./src/Removed.sol"));
+        assert!(!changed_lines.contains_line("./src/Counter.sol", 5));
+        assert!(changed_lines.contains_line("./src/Counter.sol", 6));
+        assert!(changed_lines.contains_line("./src/Counter.sol", 7));
+        assert!(changed_lines.contains_line("./src/Counter.sol", 22));
+        assert!(!changed_lines.contains_line("./src/Counter.sol", 31));
+        assert!(!changed_lines.contains_line("./src/Other.sol", 6));
+    }
+
+    #[test]
+    fn test_from_diff_quoted_path() {
+        let diff = r#"diff --git "a/src/\303\251 \"x\".sol" "b/src/\303\251 \"x\".sol"
+--- "a/src/\303\251 \"x\".sol"
++++ "b/src/\303\251 \"x\".sol"
+@@ -1 +1 @@
+-contract A {}
++contract B {}
+"#;
+        let changed_lines = ChangedLines::from_diff(diff);
+
+        assert!(changed_lines.contains_line("./src/\u{e9} \"x\".sol", 1));
+        assert_eq!(unquote("b/src/Counter.sol"), "b/src/Counter.sol");
+    }
+}
@@ -0,0 +1,408 @@
+//! The `scopelint check`, `fix` and `explain` commands, which print their results.
+
+use super::{
+    baseline, changed_lines, linter, log_error, report, rules, utils, validators,
+    walk_solidity_files,
+};
+use crate::error::{Error, Result};
+use colored::Colorize;
+use globset::Glob;
+use itertools::Itertools;
+use std::{collections::BTreeMap, ffi::OsStr, fs, io, path::PathBuf};
+
+/// Options for `scopelint check`.
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// Output format for findings.
+    pub format: report::OutputFormat,
+    /// Whether to show source code snippets in text output.
+    pub show_snippets: bool,
+    /// Only fail on findings that are not recorded in this baseline file.
+    pub baseline: Option<PathBuf>,
+    /// Record the current findings in this baseline file instead of failing on them.
+    pub write_baseline: Option<PathBuf>,
+    /// Only report findings on lines changed relative to this git ref.
+    pub changed_since: Option<String>,
+    /// With `changed_since`, report all findings in changed files instead of only those on
+    /// changed lines.
+    pub whole_files: bool,
+    /// Reuse the findings of files that did not change since the last run, see [`super::cache`].
+    pub use_cache: bool,
+    /// Files, directories or globs to check instead of the `src`, `script` and `test` directories.
+    pub paths: Vec<String>,
+    /// Check the source read from stdin as if it were the content of this file, instead of
+    /// checking files on disk.
+    pub stdin_filename: Option<PathBuf>,
+    /// The rules to report findings for.
+    pub rules: RuleFilter,
+    /// The root directory of the project, which relative paths are resolved against.
+    pub root: PathBuf,
+    /// The `.scopelint` file to use instead of the one found by searching up from the `root`.
+    pub config_file: Option<PathBuf>,
+}
+
+/// The rules selected with `--only` and `--skip`, by the names used in `.scopelint`, or
+/// [`validators::formatting::RULE`] for the formatting check.
+///
+/// All validators still run on every file and only the findings are filtered, so that inline
+/// directives for other rules are not reported as unused.
+#[derive(Debug, Clone, Default)]
+pub struct RuleFilter {
+    /// Only these rules are checked, unless empty.
+    pub only: Vec<String>,
+    /// These rules are not checked, even if they are in `only`.
+    pub skip: Vec<String>,
+}
+
+impl RuleFilter {
+    /// Returns `true` if the rule with the given name is checked.
+    #[must_use]
+    pub fn includes(&self, rule: &str) -> bool {
+        (self.only.is_empty() || self.only.iter().any(|only| only == rule)) &&
+            !self.skip.iter().any(|skip| skip == rule)
+    }
+}
+
+/// Validates the code formatting, and print details on any conventions that are not being followed
+/// in the output format given by the `check_opts`.
+/// # Errors
+/// Returns [`Error::ChecksFailed`] if the formatting or convention validations fail, or another
+/// error if they could not be run.
+pub fn run(taplo_opts: taplo::formatter::Options, check_opts: &CheckOptions) -> Result<()> {
+    // We run the formatting check separate to just indicate whether or not the user needs to format
+    // the codebase, whereas the other validators return granular information about what to fix
+    // since they currently can't be fixed automatically.
+    let valid_names = validate_conventions(check_opts);
+    // Source read from stdin is not on disk, so there is nothing for `forge fmt` to check. If the
+    // conventions could not be checked, e.g. because of an invalid `.scopelint` or paths, that
+    // error is returned and the formatting check would fail on it again.
+    let valid_fmt = if check_opts.stdin_filename.is_some() ||
+        !check_opts.rules.includes(validators::formatting::RULE) ||
+        matches!(&valid_names, Err(err) if !matches!(err, Error::ChecksFailed(_)))
+    {
+        Ok(())
+    } else {
+        validate_formatting(taplo_opts, check_opts)
+    };
+
+    checks_result(valid_names, valid_fmt)
+}
+
+/// Applies safe fixes (e.g. remove unused imports) for the selected `rules` to the files matching
+/// `paths` of the `check_opts`, or the whole project if no paths are given, then runs check.
+///
+/// # Errors
+///
+/// Returns an error if fixes could not be applied, or [`Error::ChecksFailed`] if checks still fail
+/// after fixing.
+pub fn run_fix(taplo_opts: taplo::formatter::Options, check_opts: &CheckOptions) -> Result<()> {
+    let rules = &check_opts.rules;
+    let linter = cli_linter(check_opts, false)?;
+    let results = validate(&linter, check_opts)?;
+
+    // Group the items that are reported by file.
+    let mut items_by_file: BTreeMap<&str, Vec<&utils::InvalidItem>> = BTreeMap::new();
+    for item in results
+        .items()
+        .iter()
+        .filter(|item| !item.is_disabled && !item.is_ignored && rules.includes(item.kind.name()))
+    {
+        items_by_file.entry(item.file.as_str()).or_default().push(item);
+    }
+
+    let mut fixed_files: BTreeMap<utils::ValidatorKind, usize> = BTreeMap::new();
+    for (file_path, items) in items_by_file {
+        let path = linter.root().join(file_path);
+        if !path.exists() {
+            continue;
+        }
+        let original = fs::read_to_string(&path)
+            .map_err(|err| log_error(Error::io(format!("Failed to read {file_path}"), &err)))?;
+        let mut src = original.clone();
+
+        // Fixes are applied in reverse order, see `validators::VALIDATORS`.
+        for validator in validators::VALIDATORS.iter().rev() {
+            let items: Vec<&utils::InvalidItem> =
+                items.iter().filter(|item| item.kind == validator.kind()).copied().collect();
+            if items.is_empty() {
+                continue;
+            }
+            let parsed = linter.parse(&path, &src).map_err(|err| log_error(err.into()))?;
+            if let Some(new_src) = validator.fix(&parsed, &items) {
+                src = new_src;
+                *fixed_files.entry(validator.kind()).or_default() += 1;
+            }
+        }
+        if src != original {
+            fs::write(&path, src).map_err(|err| {
+                log_error(Error::io(format!("Failed to write {file_path}"), &err))
+            })?;
+        }
+    }
+
+    for (kind, count) in fixed_files {
+        let mut description = validators::for_kind(kind).description().to_string();
+        description[..1].make_ascii_lowercase();
+        eprintln!("{}: Fixed {description} findings in {count} file(s)", "info".bold().green());
+    }
+
+    // Re-run check and report any remaining issues.
+    run(taplo_opts, check_opts)
+}
+
+/// Validates that Solidity and TOML files are formatted correctly, or only the Solidity files
+/// matching the `paths` of the `check_opts` if any are given. With the `Github` output format,
+/// each unformatted file is also printed as a workflow command so the runner annotates it.
+fn validate_formatting(
+    taplo_opts: taplo::formatter::Options,
+    check_opts: &CheckOptions,
+) -> Result<()> {
+    const GITHUB_TITLE: &str = "scopelint (fmt)";
+    let files = if check_opts.paths.is_empty() {
+        None
+    } else {
+        let linter = cli_linter(check_opts, false)?;
+        Some(solidity_files(&linter, &check_opts.paths).map_err(log_error)?)
+    };
+    let result = validators::formatting::check(taplo_opts, &check_opts.root, files.as_deref())
+        .map_err(log_error)?;
+
+    // Print any warnings/errors from `forge fmt` to stderr, so they don't end up in a document
+    // printed to stdout, e.g. with `--format json`.
+    eprint!("{}", result.forge_stderr); // Prints nothing if stderr is empty.
+
+    if check_opts.format == report::OutputFormat::Github {
+        for file in &result.unformatted_files {
+            let message = format!("{file} is not formatted, run `scopelint fmt` to fix");
+            let annotation = report::github_annotation(
+                utils::Severity::Error,
+                Some(file),
+                None,
+                GITHUB_TITLE,
+                &message,
+            );
+            println!("{annotation}");
+        }
+        if !result.forge_ok && result.unformatted_files.is_empty() {
+            let message = "Formatting validation failed, run `scopelint fmt` to fix";
+            let annotation = report::github_annotation(
+                utils::Severity::Error,
+                None,
+                None,
+                GITHUB_TITLE,
+                message,
+            );
+            println!("{annotation}");
+        }
+    }
+
+    if !result.is_valid() {
+        eprintln!(
+            "{}: Formatting validation failed, run `scopelint fmt` to fix",
+            "error".bold().red()
+        );
+        return Err(Error::ChecksFailed(report::Report::default()));
+    }
+    Ok(())
+}
+
+/// Combines the results of the convention and formatting checks. An error that kept a check from
+/// running takes precedence over failed checks, so it decides the exit code.
+fn checks_result(valid_names: Result<report::Report>, valid_fmt: Result<()>) -> Result<()> {
+    match (valid_names, valid_fmt) {
+        (Ok(_), Ok(())) => Ok(()),
+        (Err(err), _) | (_, Err(err)) if !matches!(err, Error::ChecksFailed(_)) => Err(err),
+        (Err(err), _) => Err(err),
+        (Ok(results), Err(_)) => Err(Error::ChecksFailed(results)),
+    }
+}
+
+/// Prints the explanation of the rule with the given code, or a list of all rules if no code is
+/// given.
+/// # Errors
+/// Returns [`Error::Config`] if there is no rule with the given code.
+pub fn explain(code: Option<&str>) -> Result<()> {
+    let Some(code) = code else {
+        for rule in rules::Rule::all() {
+            println!("{}  {:<28} {}", rule.code(), rule.name(), rule.summary());
+        }
+        return Ok(());
+    };
+
+    let Some(rule) = rules::Rule::from_code(code) else {
+        eprintln!(
+            "{}: Unknown rule code '{code}', run `scopelint explain` to list all rules",
+            "error".bold().red()
+        );
+        return Err(Error::Config(format!("Unknown rule code: {code}")));
+    };
+    print!("{}", rule.explain());
+    Ok(())
+}
+
+// Validates the conventions and prints the findings. Returns the report if the checks passed, and
+// `Error::ChecksFailed` with the report if they did not.
+fn validate_conventions(check_opts: &CheckOptions) -> Result<report::Report> {
+    // The source read from stdin may differ from the file on disk, so it is never cached.
+    let use_cache = check_opts.use_cache && check_opts.stdin_filename.is_none();
+    let linter = cli_linter(check_opts, use_cache)?;
+    let mut results = validate(&linter, check_opts)?;
+
+    if let Some(path) = &check_opts.write_baseline {
+        let baseline = baseline::Baseline::from_items(results.items());
+        baseline.write(&check_opts.root.join(path)).map_err(log_error)?;
+        eprintln!(
+            "{}: Wrote {} finding(s) to baseline {}",
+            "info".bold().green(),
+            baseline.len(),
+            path.display()
+        );
+        return Ok(results);
+    }
+    if let Some(path) = &check_opts.baseline {
+        let baseline = baseline::Baseline::load(&check_opts.root.join(path)).map_err(log_error)?;
+        results.apply_baseline(&baseline);
+    }
+    // Filtered after the baseline is applied, so baseline entries of other rules are not
+    // reported as fixed.
+    results.retain(|item| check_opts.rules.includes(item.kind.name()));
+    if let Some(git_ref) = &check_opts.changed_since {
+        let changed_lines =
+            changed_lines::ChangedLines::from_git(&check_opts.root, git_ref).map_err(log_error)?;
+        results.retain(|item| {
+            if check_opts.whole_files {
+                changed_lines.contains_file(&item.file)
+            } else {
+                changed_lines.contains_line(&item.file, item.line)
+            }
+        });
+    }
+    let format = check_opts.format;
+
+    // Machine-readable formats are always printed to stdout, even when there are no findings, so
+    // consumers can rely on receiving a document.
+    match format {
+        report::OutputFormat::Text => {}
+        report::OutputFormat::Json => println!("{}", results.to_json()),
+        report::OutputFormat::Sarif => println!("{}", results.to_sarif()),
+        report::OutputFormat::Github => print!("{}", results.to_github()),
+        report::OutputFormat::Junit => print!("{}", results.to_junit()),
+    }
+
+    if format == report::OutputFormat::Text {
+        eprint!("{}", results.to_text(check_opts.show_snippets));
+    }
+
+    let fixed_baseline_entries = results.fixed_baseline_entries();
+    if !fixed_baseline_entries.is_empty() {
+        eprintln!(
+            "{}: {} baseline finding(s) have been fixed, run with `--write-baseline` to update \
+             the baseline",
+            "info".bold().green(),
+            fixed_baseline_entries.len()
+        );
+        for entry in fixed_baseline_entries {
+            eprintln!("  {} ({}): {}", entry.file, entry.rule, entry.message);
+        }
+    }
+
+    let summary = results.summary();
+    if format == report::OutputFormat::Text && summary.total() > 0 {
+        eprint!("{summary}");
+    }
+
+    if !results.is_valid() {
+        eprintln!("{}: Convention checks failed, see details above", "error".bold().red());
+        return Err(Error::ChecksFailed(results));
+    }
+    let warnings = results.warning_count();
+    if warnings > 0 {
+        eprintln!(
+            "{}: Convention checks passed with {warnings} warning(s), see details above",
+            "warning".bold().yellow()
+        );
+    }
+    Ok(results)
+}
+
+// Core validation method that collects the Solidity files to check, then parses and validates them
+// with the `linter`. The files are the ones matching the `paths` of the `check_opts`, all of them
+// if no paths are given, or the `stdin_filename` with its source read from stdin.
+fn validate(linter: &linter::Linter, check_opts: &CheckOptions) -> Result<report::Report> {
+    let Some(filename) = &check_opts.stdin_filename else {
+        return if check_opts.paths.is_empty() {
+            linter.check_project()
+        } else {
+            solidity_files(linter, &check_opts.paths).and_then(|files| linter.check_files(&files))
+        }
+        .map_err(log_error);
+    };
+
+    let src = io::read_to_string(io::stdin()).map_err(|err| log_error(err.into()))?;
+    let mut results = report::Report::default();
+    if linter.is_file_ignored(filename) {
+        results.record_skipped_file();
+    } else {
+        results.record_scanned_file();
+        results.add_items(linter.check_source(filename, &src));
+    }
+    Ok(results)
+}
+
+/// Returns a linter for the project at the root of the `check_opts`.
+/// # Errors
+/// Returns [`Error::Config`] if `.scopelint` or `foundry.toml` is invalid, after logging it.
+fn cli_linter(check_opts: &CheckOptions, use_cache: bool) -> Result<linter::Linter> {
+    let root = &check_opts.root;
+    let config =
+        linter::LinterConfig::load_with_config_file(root, check_opts.config_file.as_deref())
+            .map_err(log_error)?;
+    Ok(linter::Linter::new(root.clone(), config).with_cache(use_cache))
+}
+
+/// Returns the Solidity files matching the `paths`, without duplicates.
+///
+/// Each path may be a file, a directory or a glob, which is matched against the Solidity files in
+/// the `src`, `script` and `test` directories. Paths are relative to the root of the `linter`.
+/// # Errors
+/// Returns [`Error::Config`] if a Solidity file is outside these directories, or a glob does not
+/// match any file.
+fn solidity_files(linter: &linter::Linter, paths: &[String]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for arg in paths {
+        let path = linter.root().join(arg);
+        if path.is_file() {
+            if path.extension() == Some(OsStr::new("sol")) {
+                if !linter.is_project_file(&path) {
+                    return Err(Error::Config(format!(
+                        "'{arg}' is not in the src, script or test directories"
+                    )));
+                }
+                files.push(linter.relative_path(&path));
+            }
+        } else if path.is_dir() {
+            // Files outside the `src`, `script` and `test` directories, e.g. in `lib` when the
+            // directory is the root, are not checked.
+            files.extend(
+                walk_solidity_files(&path)?
+                    .iter()
+                    .filter(|file| linter.is_project_file(file))
+                    .map(|file| linter.relative_path(file)),
+            );
+        } else {
+            let matcher = Glob::new(arg.trim_start_matches("./"))
+                .map_err(|err| Error::Config(format!("Invalid path or glob '{arg}': {err}")))?
+                .compile_matcher();
+            let glob_files: Vec<PathBuf> = linter
+                .project_files()?
+                .into_iter()
+                .filter(|file| matcher.is_match(file.strip_prefix("./").unwrap_or(file)))
+                .collect();
+            if glob_files.is_empty() {
+                return Err(Error::Config(format!("No Solidity files match '{arg}'")));
+            }
+            files.extend(glob_files);
+        }
+    }
+    Ok(files.into_iter().unique().collect())
+}
@@ -240,18 +240,22 @@ impl Comments {
     }
 
     /// Parse all comments to return a list of inline config items. This will return an iterator of
-    /// results of parsing comments which start with `scopelint:`
+    /// results of parsing comments which start with `scopelint:`, along with the reason given
+    /// after `--`, if any
     pub fn parse_inline_config_items(
         &self,
-    ) -> impl Iterator<Item = Result<(Loc, InlineConfigItem), (Loc, InvalidInlineConfigItem)>> + '_
-    {
+    ) -> impl Iterator<
+        Item = Result<(Loc, InlineConfigItem, Option<String>), (Loc, InvalidInlineConfigItem)>,
+    > + '_ {
         self.iter()
             .filter_map(|comment| {
                 Some((comment, comment.contents().trim_start().strip_prefix("scopelint:")?.trim()))
             })
             .map(|(comment, item)| {
                 let loc = comment.loc;
-                item.parse().map(|out| (loc, out)).map_err(|out| (loc, out))
+                InlineConfigItem::parse_with_reason(item)
+                    .map(|(out, reason)| (loc, out, reason))
+                    .map_err(|out| (loc, out))
             })
     }
 }
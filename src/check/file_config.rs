@@ -3,6 +3,8 @@
 //! Supports:
 //! - File-level ignores (entire files)
 //! - Rule-specific ignores per file (overrides)
+//! - Requiring a reason on inline ignore directives
+//! - Project-wide rule severities (`error`, `warn` or `off`)
 //!
 //! Format:
 //! ```toml
@@ -12,61 +14,83 @@
 //!     "src/legacy/old.sol",
 //!     "test/integration/*.sol"
 //! ]
+//! # Report inline ignore directives without a reason, e.g. `// scopelint: ignore-line -- reason`
+//! require_reason = true
 //!
 //! # Ignore specific rules for specific files
 //! [ignore.overrides]
 //! "src/BaseBridgeReceiver.sol" = ["src"]
 //! "src/legacy/**/*.sol" = ["src", "error"]
+//!
+//! # Report findings of a rule as warnings, or turn the rule off
+//! [rules]
+//! eip712 = "warn"
+//! import = "off"
 //! ```
 
-use crate::check::utils::ValidatorKind;
+use crate::{
+    check::{
+        utils::{Severity, ValidatorKind},
+        validators,
+    },
+    error::Error,
+};
 use globset::{Glob, GlobMatcher};
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 /// Configuration loaded from `.scopelint` file
 #[derive(Debug, Default, Clone)]
 pub struct FileConfig {
-    /// Directory where the `.scopelint` file was found (project root)
-    config_dir: Option<PathBuf>,
+    /// The `.scopelint` file the config was loaded from, if any
+    config_file: Option<PathBuf>,
     /// Patterns for files to ignore entirely
     ignored_file_patterns: Vec<GlobMatcher>,
     /// Rule-specific overrides: file pattern -> list of rules to ignore
     rule_overrides: Vec<(GlobMatcher, Vec<ValidatorKind>)>,
+    /// Project-wide severity of rules, rules that are not listed use the validator's default
+    rule_severities: HashMap<ValidatorKind, Severity>,
+    /// Whether inline ignore directives must give a reason
+    require_ignore_reason: bool,
 }
 
 impl FileConfig {
-    /// Load configuration from `.scopelint` file.
-    /// Searches up the directory tree from the current working directory to find the file.
-    /// Returns default config if file doesn't exist or can't be parsed.
+    /// Load configuration from the `.scopelint` file of the project at `root`.
+    /// Searches up the directory tree from `root` to find the file, and returns the default config
+    /// if there is none.
+    /// # Errors
+    /// Returns an error if the file can't be read or parsed.
+    pub fn load_from(root: &Path) -> Result<Self, Error> {
+        Self::find_config_file_from(root)
+            .map_or_else(|| Ok(Self::default()), |config_path| Self::load_file(&config_path))
+    }
+
+    /// Load configuration from the `.scopelint` file at `config_file`, which may be outside the
+    /// project.
+    /// # Errors
+    /// Returns an error if the file can't be read or parsed.
+    pub fn load_file(config_file: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(config_file)
+            .map_err(|err| Error::io(format!("Failed to read {}", config_file.display()), &err))?;
+        let mut config = Self::from_toml(&content).map_err(|err| {
+            Error::Config(format!("Failed to parse {}: {err}", config_file.display()))
+        })?;
+        config.config_file = Some(config_file.to_path_buf());
+        Ok(config)
+    }
+
+    /// The `.scopelint` file the config was loaded from, or `None` if the default config is used.
     #[must_use]
-    pub fn load() -> Self {
-        let config_path = Self::find_config_file();
-        let Some(config_path) = config_path else {
-            return Self::default();
-        };
-
-        let config_dir = config_path.parent().map(PathBuf::from);
-
-        match std::fs::read_to_string(&config_path) {
-            Ok(content) => {
-                let mut config = Self::from_toml(&content).unwrap_or_else(|err| {
-                    eprintln!("Warning: Failed to parse .scopelint: {err}. Using default config.");
-                    Self::default()
-                });
-                config.config_dir = config_dir;
-                config
-            }
-            Err(err) => {
-                eprintln!("Warning: Failed to read .scopelint: {err}. Using default config.");
-                Self::default()
-            }
-        }
+    pub fn config_file(&self) -> Option<&Path> {
+        self.config_file.as_deref()
     }
 
-    /// Search up the directory tree to find `.scopelint` file.
+    /// Search up the directory tree from `dir` to find `.scopelint` file.
     /// Returns the path to the config file if found, None otherwise.
-    fn find_config_file() -> Option<PathBuf> {
-        let mut current_dir = std::env::current_dir().ok()?;
+    pub(crate) fn find_config_file_from(dir: &Path) -> Option<PathBuf> {
+        let mut current_dir = dir.to_path_buf();
 
         loop {
             let config_path = current_dir.join(".scopelint");
@@ -104,6 +128,12 @@ impl FileConfig {
                 }
             }
 
+            if let Some(require_reason) = ignore_section.get("require_reason") {
+                config.require_ignore_reason = require_reason
+                    .as_bool()
+                    .ok_or_else(|| "require_reason must be a boolean".to_string())?;
+            }
+
             // Parse [ignore.overrides] section
             if let Some(overrides) = ignore_section.get("overrides").and_then(|v| v.as_table()) {
                 for (pattern_str, rules_value) in overrides {
@@ -131,13 +161,45 @@ impl FileConfig {
             }
         }
 
+        // Parse [rules] section
+        if let Some(rules) = toml.get("rules") {
+            let rules = rules.as_table().ok_or_else(|| "[rules] must be a table".to_string())?;
+            for (rule_name, severity_value) in rules {
+                let kind = parse_rule_name(rule_name)
+                    .ok_or_else(|| format!("Unknown rule: '{rule_name}'"))?;
+                let severity =
+                    severity_value.as_str().and_then(Severity::from_name).ok_or_else(|| {
+                        format!(
+                            "Severity for '{rule_name}' must be one of 'error', 'warn' or 'off'"
+                        )
+                    })?;
+                config.rule_severities.insert(kind, severity);
+            }
+        }
+
         Ok(config)
     }
 
-    /// Check if a file should be ignored entirely
+    /// Get the configured severity of a rule
+    #[must_use]
+    pub fn severity(&self, kind: &ValidatorKind) -> Severity {
+        self.rule_severities
+            .get(kind)
+            .copied()
+            .unwrap_or_else(|| validators::for_kind(*kind).default_severity())
+    }
+
+    /// Whether inline ignore directives without a reason are reported
+    #[must_use]
+    pub const fn requires_ignore_reason(&self) -> bool {
+        self.require_ignore_reason
+    }
+
+    /// Check if a file should be ignored entirely. Like all paths given to the config, `file_path`
+    /// is relative to the project root, see [`crate::Linter::relative_path`].
     #[must_use]
     pub fn is_file_ignored(&self, file_path: &Path) -> bool {
-        let normalized = self.normalize_path(file_path);
+        let normalized = Self::normalize_path(file_path);
 
         self.ignored_file_patterns.iter().any(|matcher| matcher.is_match(&normalized))
     }
@@ -145,35 +207,22 @@ impl FileConfig {
     /// Get list of rules to ignore for a specific file
     #[must_use]
     pub fn get_ignored_rules(&self, file_path: &Path) -> Vec<ValidatorKind> {
-        let normalized = self.normalize_path(file_path);
+        let normalized = Self::normalize_path(file_path);
 
         let mut ignored_rules = Vec::new();
         for (matcher, rules) in &self.rule_overrides {
             if matcher.is_match(&normalized) {
-                ignored_rules.extend(rules.iter().cloned());
+                ignored_rules.extend(rules.iter().copied());
             }
         }
         ignored_rules
     }
 
-    /// Normalize file path for glob matching:
-    /// - Convert to relative path from config directory (project root)
+    /// Normalize file path, relative to the project root, for glob matching:
     /// - Normalize path separators to forward slashes
-    fn normalize_path(&self, file_path: &Path) -> String {
-        // Use config directory as base, fallback to current directory if no config found
-        let base_dir = self.config_dir.as_ref().map_or_else(
-            || std::env::current_dir().ok().unwrap_or_else(|| PathBuf::from(".")),
-            Clone::clone,
-        );
-
-        // Try to get relative path from base directory
-        let relative = if file_path.is_absolute() {
-            file_path.strip_prefix(&base_dir).unwrap_or(file_path)
-        } else {
-            file_path
-        };
-
-        let file_str = relative.to_string_lossy();
+    /// - Strip the leading `./`
+    fn normalize_path(file_path: &Path) -> String {
+        let file_str = file_path.to_string_lossy();
         // Normalize path separators for glob matching (Windows uses backslashes)
         let normalized = file_str.replace('\\', "/");
         // Strip leading "./" if present, as glob patterns don't expect it
@@ -186,18 +235,8 @@ impl FileConfig {
 }
 
 /// Maps a rule name (e.g., "error") to a `ValidatorKind`
-fn parse_rule_name(rule: &str) -> Option<ValidatorKind> {
-    match rule {
-        "error" => Some(ValidatorKind::Error),
-        "import" => Some(ValidatorKind::Import),
-        "variable" => Some(ValidatorKind::Variable),
-        "constant" => Some(ValidatorKind::Constant),
-        "test" => Some(ValidatorKind::Test),
-        "script" => Some(ValidatorKind::Script),
-        "src" => Some(ValidatorKind::Src),
-        "eip712" => Some(ValidatorKind::Eip712),
-        _ => None,
-    }
+pub(crate) fn parse_rule_name(rule: &str) -> Option<ValidatorKind> {
+    validators::kind_from_id(rule)
 }
 
 #[cfg(test)]
@@ -224,11 +263,9 @@ files = ["src/legacy.sol", "test/integration/*.sol"]
 "src/BaseBridgeReceiver.sol" = ["src"]
 "src/legacy/**/*.sol" = ["src", "error"]
 "#;
-        let mut config = FileConfig::from_toml(toml).unwrap();
-        // Set config_dir to simulate real scenario
-        config.config_dir = Some(PathBuf::from("."));
+        let config = FileConfig::from_toml(toml).unwrap();
 
-        let ignored = config.get_ignored_rules(Path::new("src/BaseBridgeReceiver.sol"));
+        let ignored = config.get_ignored_rules(Path::new("./src/BaseBridgeReceiver.sol"));
         assert_eq!(ignored, vec![ValidatorKind::Src]);
 
         let ignored = config.get_ignored_rules(Path::new("src/legacy/old.sol"));
@@ -237,10 +274,40 @@ files = ["src/legacy.sol", "test/integration/*.sol"]
         assert!(ignored.contains(&ValidatorKind::Error));
     }
 
+    #[test]
+    fn test_parse_rule_severities() {
+        let toml = r#"
+[rules]
+eip712 = "warn"
+import = "off"
+error = "error"
+"#;
+        let config = FileConfig::from_toml(toml).unwrap();
+
+        assert_eq!(config.severity(&ValidatorKind::Eip712), Severity::Warn);
+        assert_eq!(config.severity(&ValidatorKind::Import), Severity::Off);
+        assert_eq!(config.severity(&ValidatorKind::Error), Severity::Error);
+        assert_eq!(config.severity(&ValidatorKind::Test), Severity::Error);
+    }
+
+    #[test]
+    fn test_parse_invalid_rule_severities() {
+        assert!(FileConfig::from_toml("[rules]\nimport = \"loud\"").is_err());
+        assert!(FileConfig::from_toml("[rules]\nunknown = \"warn\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_require_reason() {
+        let config = FileConfig::from_toml("[ignore]\nrequire_reason = true").unwrap();
+        assert!(config.requires_ignore_reason());
+        assert!(FileConfig::from_toml("[ignore]\nrequire_reason = \"yes\"").is_err());
+    }
+
     #[test]
     fn test_parse_empty_config() {
         let config = FileConfig::from_toml("").unwrap();
         assert!(!config.is_file_ignored(Path::new("src/test.sol")));
         assert!(config.get_ignored_rules(Path::new("src/test.sol")).is_empty());
+        assert!(!config.requires_ignore_reason());
     }
 }
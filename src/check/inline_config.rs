@@ -8,7 +8,12 @@
 // - `// scopelint: ignore-error-start` / `// scopelint: ignore-error-end` - ignore a region
 // - `// scopelint: ignore-error-file` - ignores entire file for error_prefix validator
 //
-// Supported rules: error, import, variable, constant, test, script, src, eip712
+// Supported rules are the ids of the validators in `validators::VALIDATORS`, e.g. error, import,
+// variable, constant, test, script, src, eip712
+//
+// Any directive may be followed by a reason after ` -- `, e.g.
+// `// scopelint: ignore-error-next-line -- legacy ABI compatibility`. The reason is kept on the
+// findings suppressed by the directive.
 
 // We disable clippy in this file to keep this file as close to the original as possible, so it's
 // easier to merge in upstream changes.
@@ -16,10 +21,11 @@
 use crate::check::{
     comments::{CommentState, CommentStringExt},
     utils::ValidatorKind,
+    validators,
 };
 use itertools::Itertools;
 use solang_parser::pt::Loc;
-use std::{fmt, str::FromStr};
+use std::{cell::Cell, fmt, str::FromStr};
 
 /// An inline config item
 #[allow(clippy::enum_variant_names)]
@@ -108,6 +114,33 @@ impl FromStr for InlineConfigItem {
     }
 }
 
+impl InlineConfigItem {
+    /// Parses a directive followed by an optional reason, separated by `--`, e.g.
+    /// `ignore-next-line -- legacy ABI compatibility`. An empty reason is treated as no reason.
+    pub fn parse_with_reason(s: &str) -> Result<(Self, Option<String>), InvalidInlineConfigItem> {
+        let (directive, reason) = match s.split_once("--") {
+            Some((directive, reason)) => (directive.trim(), Some(reason.trim())),
+            None => (s.trim(), None),
+        };
+        let item = directive.parse()?;
+        let reason = reason.filter(|reason| !reason.is_empty()).map(str::to_string);
+        Ok((item, reason))
+    }
+
+    /// Returns `true` if the directive starts ignoring findings, i.e. it is an ignore directive
+    /// other than the end of an ignore region.
+    pub fn is_ignore(&self) -> bool {
+        match self {
+            InlineConfigItem::IgnoreNextItem |
+            InlineConfigItem::IgnoreLine |
+            InlineConfigItem::IgnoreNextLine |
+            InlineConfigItem::IgnoreStart => true,
+            InlineConfigItem::IgnoreRule { scope, .. } => !matches!(scope, RuleIgnoreScope::End),
+            _ => false,
+        }
+    }
+}
+
 /// The scope of a rule-specific ignore directive
 #[derive(Debug, Clone, Copy)]
 pub enum RuleIgnoreScope {
@@ -127,17 +160,7 @@ pub enum RuleIgnoreScope {
 
 /// Maps a rule name (e.g., "error") to a `ValidatorKind`
 fn parse_rule_name(rule: &str) -> Option<ValidatorKind> {
-    match rule {
-        "error" => Some(ValidatorKind::Error),
-        "import" => Some(ValidatorKind::Import),
-        "variable" => Some(ValidatorKind::Variable),
-        "constant" => Some(ValidatorKind::Constant),
-        "test" => Some(ValidatorKind::Test),
-        "script" => Some(ValidatorKind::Script),
-        "src" => Some(ValidatorKind::Src),
-        "eip712" => Some(ValidatorKind::Eip712),
-        _ => None,
-    }
+    validators::kind_from_id(rule)
 }
 
 #[derive(Debug)]
@@ -174,14 +197,44 @@ impl DisabledRange {
 /// An ignored formatting range. `loose` designates that the range includes any loc which
 /// may start in between start and end, whereas the strict version requires that
 /// `range.start >= loc.start <=> loc.end <= range.end`
+/// `directives` are the locations of the comments that created the range, `reason` is the reason
+/// given on them, and `used` records whether the range suppressed at least one finding.
 #[derive(Debug)]
 struct IgnoredRange {
     start: usize,
     end: usize,
     loose: bool,
+    directives: Vec<Loc>,
+    reason: Option<String>,
+    used: Cell<bool>,
 }
 
 impl IgnoredRange {
+    fn new(
+        start: usize,
+        end: usize,
+        loose: bool,
+        directives: Vec<Loc>,
+        reason: Option<String>,
+    ) -> Self {
+        Self { start, end, loose, directives, reason, used: Cell::new(false) }
+    }
+
+    /// Checks if the range applies to the location. A directive never applies to itself, so an
+    /// unused `ignore-line` directive is still reported.
+    fn applies_to(&self, loc: Loc) -> bool {
+        !self.directives.contains(&loc) && self.includes(loc)
+    }
+
+    /// Checks if the range applies to the location, and if so marks the range as used
+    fn matches(&self, loc: Loc) -> bool {
+        let includes = self.applies_to(loc);
+        if includes {
+            self.used.set(true);
+        }
+        includes
+    }
+
     fn includes(&self, loc: Loc) -> bool {
         if self.loose {
             // For loose ranges, check if location starts within the range [start, end)
@@ -202,12 +255,17 @@ pub struct InlineConfig {
     ignored_ranges: Vec<IgnoredRange>,
     /// Rule-specific ignored ranges, keyed by ValidatorKind
     rule_ignored_ranges: std::collections::HashMap<ValidatorKind, Vec<IgnoredRange>>,
+    /// Locations of the ignore directives that do not give a reason
+    unjustified_directives: Vec<Loc>,
 }
 
 impl InlineConfig {
     /// Build a new inline config with an iterator of inline config items and their locations in a
-    /// source file
-    pub fn new(items: impl IntoIterator<Item = (Loc, InlineConfigItem)>, src: &str) -> Self {
+    /// source file. Each item may carry the reason given for it.
+    pub fn new(
+        items: impl IntoIterator<Item = (Loc, InlineConfigItem, Option<String>)>,
+        src: &str,
+    ) -> Self {
         // Disable ranges (for formatting)
         let mut disabled_ranges = vec![];
         let mut disabled_range_start = None;
@@ -216,6 +274,8 @@ impl InlineConfig {
         // Ignore ranges (for linting)
         let mut ignored_ranges = vec![];
         let mut ignored_range_start = None;
+        let mut ignored_range_directives = vec![];
+        let mut ignored_range_reason = None;
         let mut ignored_depth = 0usize;
 
         // Rule-specific ignore ranges
@@ -225,8 +285,18 @@ impl InlineConfig {
             std::collections::HashMap::new();
         let mut rule_ignored_depths: std::collections::HashMap<ValidatorKind, usize> =
             std::collections::HashMap::new();
+        let mut rule_ignored_directives: std::collections::HashMap<ValidatorKind, Vec<Loc>> =
+            std::collections::HashMap::new();
+        let mut rule_ignored_reasons: std::collections::HashMap<ValidatorKind, Option<String>> =
+            std::collections::HashMap::new();
+
+        // Ignore directives that do not give a reason
+        let mut unjustified_directives = vec![];
 
-        for (loc, item) in items.into_iter().sorted_by_key(|(loc, _)| loc.start()) {
+        for (loc, item, reason) in items.into_iter().sorted_by_key(|(loc, ..)| loc.start()) {
+            if item.is_ignore() && reason.is_none() {
+                unjustified_directives.push(loc);
+            }
             match item {
                 InlineConfigItem::DisableNextItem => {
                     let offset = loc.end();
@@ -318,7 +388,7 @@ impl InlineConfig {
                                 }
                             }
                         }
-                        ignored_ranges.push(IgnoredRange { start, end, loose: true });
+                        ignored_ranges.push(IgnoredRange::new(start, end, true, vec![loc], reason));
                     }
                 }
                 InlineConfigItem::IgnoreLine => {
@@ -332,7 +402,7 @@ impl InlineConfig {
                     let end =
                         end_offset + next_newline.next().map(|(idx, _)| idx).unwrap_or_default();
 
-                    ignored_ranges.push(IgnoredRange { start, end, loose: false });
+                    ignored_ranges.push(IgnoredRange::new(start, end, false, vec![loc], reason));
                 }
                 InlineConfigItem::IgnoreNextLine => {
                     let offset = loc.end();
@@ -344,24 +414,38 @@ impl InlineConfig {
                             .find(|(_, ch)| *ch == '\n')
                             .map(|(idx, _)| offset + idx + 1)
                             .unwrap_or(src.len());
-                        ignored_ranges.push(IgnoredRange { start, end, loose: false });
+                        ignored_ranges.push(IgnoredRange::new(
+                            start,
+                            end,
+                            false,
+                            vec![loc],
+                            reason,
+                        ));
                     }
                 }
                 InlineConfigItem::IgnoreStart => {
                     if ignored_depth == 0 {
                         ignored_range_start = Some(loc.end());
+                        ignored_range_directives.clear();
+                        ignored_range_reason = None;
                     }
+                    ignored_range_directives.push(loc);
+                    ignored_range_reason = ignored_range_reason.or(reason);
                     ignored_depth += 1;
                 }
                 InlineConfigItem::IgnoreEnd => {
                     ignored_depth = ignored_depth.saturating_sub(1);
+                    ignored_range_directives.push(loc);
+                    ignored_range_reason = ignored_range_reason.or(reason);
                     if ignored_depth == 0 {
                         if let Some(start) = ignored_range_start.take() {
-                            ignored_ranges.push(IgnoredRange {
+                            ignored_ranges.push(IgnoredRange::new(
                                 start,
-                                end: loc.start(),
-                                loose: false,
-                            })
+                                loc.start(),
+                                false,
+                                std::mem::take(&mut ignored_range_directives),
+                                ignored_range_reason.take(),
+                            ))
                         }
                     }
                 }
@@ -370,7 +454,10 @@ impl InlineConfig {
                     let ranges = rule_ignored_ranges.entry(kind.clone()).or_insert_with(Vec::new);
                     let range_start =
                         rule_ignored_starts.entry(kind.clone()).or_insert_with(|| None);
-                    let depth = rule_ignored_depths.entry(kind).or_insert_with(|| 0);
+                    let depth = rule_ignored_depths.entry(kind.clone()).or_insert_with(|| 0);
+                    let directives =
+                        rule_ignored_directives.entry(kind.clone()).or_insert_with(Vec::new);
+                    let range_reason = rule_ignored_reasons.entry(kind).or_insert_with(|| None);
 
                     match scope {
                         RuleIgnoreScope::NextItem => {
@@ -402,7 +489,7 @@ impl InlineConfig {
                                         }
                                     }
                                 }
-                                ranges.push(IgnoredRange { start, end, loose: true });
+                                ranges.push(IgnoredRange::new(start, end, true, vec![loc], reason));
                             }
                         }
                         RuleIgnoreScope::Line => {
@@ -418,7 +505,7 @@ impl InlineConfig {
                             let end = end_offset +
                                 next_newline.next().map(|(idx, _)| idx).unwrap_or_default();
 
-                            ranges.push(IgnoredRange { start, end, loose: false });
+                            ranges.push(IgnoredRange::new(start, end, false, vec![loc], reason));
                         }
                         RuleIgnoreScope::NextLine => {
                             let offset = loc.end();
@@ -434,24 +521,32 @@ impl InlineConfig {
                                     .unwrap_or(src.len());
                                 // Use loose: true to include locations that might extend slightly
                                 // beyond the line
-                                ranges.push(IgnoredRange { start, end, loose: true });
+                                ranges.push(IgnoredRange::new(start, end, true, vec![loc], reason));
                             }
                         }
                         RuleIgnoreScope::Start => {
                             if *depth == 0 {
                                 *range_start = Some(loc.end());
+                                directives.clear();
+                                *range_reason = None;
                             }
+                            directives.push(loc);
+                            *range_reason = range_reason.take().or(reason);
                             *depth += 1;
                         }
                         RuleIgnoreScope::End => {
                             *depth = depth.saturating_sub(1);
+                            directives.push(loc);
+                            *range_reason = range_reason.take().or(reason);
                             if *depth == 0 {
                                 if let Some(start) = range_start.take() {
-                                    ranges.push(IgnoredRange {
+                                    ranges.push(IgnoredRange::new(
                                         start,
-                                        end: loc.end(),
-                                        loose: false,
-                                    })
+                                        loc.end(),
+                                        false,
+                                        std::mem::take(directives),
+                                        range_reason.take(),
+                                    ))
                                 }
                             }
                         }
@@ -460,7 +555,13 @@ impl InlineConfig {
                             // Use loose: true to ensure any location in the file is covered
                             // For loose ranges with < check, use src.len() + 1 to include all valid
                             // offsets
-                            ranges.push(IgnoredRange { start: 0, end: src.len() + 1, loose: true });
+                            ranges.push(IgnoredRange::new(
+                                0,
+                                src.len() + 1,
+                                true,
+                                vec![loc],
+                                reason,
+                            ));
                         }
                     }
                 }
@@ -470,16 +571,24 @@ impl InlineConfig {
             disabled_ranges.push(DisabledRange { start, end: src.len(), loose: false })
         }
         if let Some(start) = ignored_range_start.take() {
-            ignored_ranges.push(IgnoredRange { start, end: src.len(), loose: false })
+            ignored_ranges.push(IgnoredRange::new(
+                start,
+                src.len(),
+                false,
+                ignored_range_directives,
+                ignored_range_reason,
+            ))
         }
         // Handle unclosed rule-specific ignore regions
         for (kind, range_start) in rule_ignored_starts {
             if let Some(start) = range_start {
                 let ranges = rule_ignored_ranges.entry(kind).or_insert_with(Vec::new);
-                ranges.push(IgnoredRange { start, end: src.len(), loose: false });
+                let directives = rule_ignored_directives.remove(&kind).unwrap_or_default();
+                let reason = rule_ignored_reasons.remove(&kind).flatten();
+                ranges.push(IgnoredRange::new(start, src.len(), false, directives, reason));
             }
         }
-        Self { disabled_ranges, ignored_ranges, rule_ignored_ranges }
+        Self { disabled_ranges, ignored_ranges, rule_ignored_ranges, unjustified_directives }
     }
 
     /// Check if the location is in a disabled range
@@ -487,15 +596,72 @@ impl InlineConfig {
         self.disabled_ranges.iter().any(|range| range.includes(loc))
     }
 
-    /// Check if the location is in an ignored range (generic ignore)
+    /// Check if the location is in an ignored range (generic ignore). Every matching range is
+    /// marked as used.
     pub fn is_ignored(&self, loc: Loc) -> bool {
-        self.ignored_ranges.iter().any(|range| range.includes(loc))
+        self.ignored_ranges.iter().filter(|range| range.matches(loc)).count() > 0
     }
 
-    /// Check if the location is in an ignored range for a specific validator kind
+    /// Check if the location is in an ignored range for a specific validator kind. Every matching
+    /// range is marked as used.
     pub fn is_rule_ignored(&self, loc: Loc, kind: &ValidatorKind) -> bool {
         self.rule_ignored_ranges
             .get(kind)
-            .map_or(false, |ranges| ranges.iter().any(|range| range.includes(loc)))
+            .map_or(false, |ranges| ranges.iter().filter(|range| range.matches(loc)).count() > 0)
+    }
+
+    /// Returns the reason given on the first ignore directive, generic or for the validator kind,
+    /// that applies to the location. Ranges are not marked as used.
+    pub fn ignore_reason(&self, loc: Loc, kind: &ValidatorKind) -> Option<&str> {
+        self.ignored_ranges
+            .iter()
+            .chain(self.rule_ignored_ranges.get(kind).into_iter().flatten())
+            .filter(|range| range.applies_to(loc))
+            .find_map(|range| range.reason.as_deref())
+    }
+
+    /// Returns the locations of the ignore directives that do not give a reason, sorted by
+    /// position.
+    pub fn ignore_directives_without_reason(&self) -> &[Loc] {
+        &self.unjustified_directives
+    }
+
+    /// Returns the locations of the ignore directives whose ranges have not matched any location
+    /// passed to [`Self::is_ignored`] or [`Self::is_rule_ignored`], sorted by position. Must be
+    /// called after all validators have run on the file.
+    pub fn unused_ignore_directives(&self) -> Vec<Loc> {
+        self.ignored_ranges
+            .iter()
+            .chain(self.rule_ignored_ranges.values().flatten())
+            .filter(|range| !range.used.get())
+            .flat_map(|range| range.directives.iter().copied())
+            .sorted_by_key(|loc| loc.start())
+            .dedup()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_with_reason() {
+        let (item, reason) =
+            InlineConfigItem::parse_with_reason("ignore-error-next-line -- legacy ABI").unwrap();
+        assert!(matches!(
+            item,
+            InlineConfigItem::IgnoreRule {
+                kind: ValidatorKind::Error,
+                scope: RuleIgnoreScope::NextLine
+            }
+        ));
+        assert_eq!(reason.as_deref(), Some("legacy ABI"));
+
+        let (item, reason) = InlineConfigItem::parse_with_reason("ignore-next-line --").unwrap();
+        assert!(matches!(item, InlineConfigItem::IgnoreNextLine));
+        assert_eq!(reason, None);
+
+        assert!(InlineConfigItem::parse_with_reason("ignore-nxt-line -- typo").is_err());
     }
 }
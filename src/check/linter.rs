@@ -0,0 +1,336 @@
+//! Checks the Solidity files of a project when scopelint is used as a library.
+//!
+//! A [`Linter`](crate::Linter) is bound to the root directory of a project and never reads the
+//! current directory or prints anything, so it can be embedded in other tools. Findings are
+//! returned to the caller, and errors are returned instead of being logged. The `scopelint`
+//! commands are built on top of it and take care of printing the results.
+//!
+//! ```no_run
+//! use scopelint::{Linter, LinterConfig};
+//! use std::path::Path;
+//!
+//! let root = Path::new("/path/to/project");
+//! let linter = Linter::new(root, LinterConfig::load(root)?);
+//! let items = linter.check_source(Path::new("src/Counter.sol"), "contract Counter {}");
+//! let report = linter.check_project()?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use super::{
+    cache::{content_hash, Cache},
+    file_config::FileConfig,
+    parse_source,
+    report::Report,
+    utils::{InvalidItem, Severity},
+    validators::VALIDATORS,
+    walk_solidity_files, ParseError, Parsed,
+};
+use crate::{
+    error::{Error, Result},
+    foundry_config::CheckPaths,
+};
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The configuration a [`Linter`] checks files with.
+#[derive(Debug, Clone, Default)]
+pub struct LinterConfig {
+    /// The rules, severities and ignored files from `.scopelint`.
+    pub file_config: FileConfig,
+    /// The `src`, `script` and `test` directories from `foundry.toml`.
+    pub path_config: CheckPaths,
+}
+
+impl LinterConfig {
+    /// Loads the `.scopelint` and `foundry.toml` files in effect for the project at `root`,
+    /// searching up the directory tree like the `scopelint` commands do. Missing files use the
+    /// defaults.
+    /// # Errors
+    /// Returns an error if a config file can't be read or parsed.
+    pub fn load(root: &Path) -> Result<Self> {
+        Self::load_with_config_file(root, None)
+    }
+
+    /// Like [`Self::load`], but reads `.scopelint` from `config_file` instead of searching for it,
+    /// if given.
+    /// # Errors
+    /// Returns an error if a config file can't be read or parsed.
+    pub fn load_with_config_file(root: &Path, config_file: Option<&Path>) -> Result<Self> {
+        let file_config = match config_file {
+            Some(config_file) => FileConfig::load_file(config_file)?,
+            None => FileConfig::load_from(root)?,
+        };
+        Ok(Self { file_config, path_config: CheckPaths::load_from(root)? })
+    }
+}
+
+/// Checks the Solidity files of the project at a root directory.
+#[derive(Debug, Clone)]
+pub struct Linter {
+    /// The project root that paths are relative to.
+    root: PathBuf,
+    /// The configuration files are checked with.
+    config: LinterConfig,
+    /// Whether the findings of files are cached in the project, see [`super::cache`].
+    use_cache: bool,
+}
+
+impl Linter {
+    /// Creates a linter for the project at `root`. The cache is disabled, see
+    /// [`Self::with_cache`].
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>, config: LinterConfig) -> Self {
+        Self { root: root.into(), config, use_cache: false }
+    }
+
+    /// Sets whether the findings of files that did not change are reused from the cache in the
+    /// project root, see [`super::cache`]. Failing to write the cache is not an error, it only
+    /// makes the next check slower.
+    #[must_use]
+    pub const fn with_cache(mut self, use_cache: bool) -> Self {
+        self.use_cache = use_cache;
+        self
+    }
+
+    /// Returns the project root.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the configuration files are checked with.
+    #[must_use]
+    pub const fn config(&self) -> &LinterConfig {
+        &self.config
+    }
+
+    /// Parses the given source code of the file at `path` and runs all validators on it. A file
+    /// that cannot be parsed is reported as [`super::rules::Rule::ParseError`] findings, and a file
+    /// that is ignored in `.scopelint` has no findings.
+    ///
+    /// The path may be absolute or relative to the project root, and the file does not need to
+    /// exist. The source is never cached, since it may differ from the file on disk.
+    ///
+    /// Findings of rules that are `off` in `.scopelint` are left out. Findings that are suppressed
+    /// are still returned, so callers can count them: `is_disabled` and `is_ignored` are set for
+    /// findings in regions disabled or ignored by inline directives, and `is_ignored` and
+    /// `is_ignored_by_config` for rules ignored for the file in `.scopelint`. Use
+    /// [`InvalidItem::is_suppressed`] to only keep the findings that are reported.
+    #[must_use]
+    pub fn check_source(&self, path: &Path, src: &str) -> Vec<InvalidItem> {
+        let file = self.relative_path(path);
+        if self.config.file_config.is_file_ignored(&file) {
+            return Vec::new();
+        }
+        self.check_file_source(&file, src)
+    }
+
+    /// Checks all Solidity files in the `src`, `script` and `test` directories. Cached findings of
+    /// files that no longer exist are removed.
+    /// # Errors
+    /// Returns an error if a directory can't be walked or a file can't be read.
+    pub fn check_project(&self) -> Result<Report> {
+        self.check(&self.project_files()?, true)
+    }
+
+    /// Checks the given Solidity files. Paths may be absolute or relative to the project root.
+    /// # Errors
+    /// Returns an error if a file can't be read.
+    pub fn check_files(&self, files: &[PathBuf]) -> Result<Report> {
+        self.check(files, false)
+    }
+
+    /// Returns the Solidity files in the `src`, `script` and `test` directories, relative to the
+    /// project root, in the order they are walked.
+    /// # Errors
+    /// Returns an error if a directory can't be walked.
+    pub fn project_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for dir in self.config.path_config.as_array() {
+            let dir_path = self.root.join(dir);
+            for file in walk_solidity_files(&dir_path)? {
+                // Keep the directory as configured, so files are classified against it.
+                let file = file
+                    .strip_prefix(&dir_path)
+                    .map_or_else(|_| file.clone(), |rel| Path::new(dir).join(rel));
+                files.push(self.relative_path(&file));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Returns `true` if the file is in the `src`, `script` or `test` directory of the project.
+    #[must_use]
+    pub fn is_project_file(&self, path: &Path) -> bool {
+        let path = self.relative_path(path);
+        self.config.path_config.as_array().iter().any(|dir| path.starts_with(dir))
+    }
+
+    /// Returns `true` if the file is ignored entirely in `.scopelint`.
+    #[must_use]
+    pub fn is_file_ignored(&self, path: &Path) -> bool {
+        self.config.file_config.is_file_ignored(&self.relative_path(path))
+    }
+
+    /// Parses the given source code of the file at `path` and returns a [`Parsed`] struct with
+    /// the configuration attached, ready to be validated or fixed.
+    /// # Errors
+    /// Returns the parser diagnostics if the source code cannot be parsed.
+    pub fn parse(&self, path: &Path, src: &str) -> Result<Parsed, ParseError> {
+        let mut parsed = parse_source(&self.relative_path(path), src)?;
+        parsed.file_config = self.config.file_config.clone();
+        parsed.path_config = self.config.path_config.clone();
+        Ok(parsed)
+    }
+
+    /// Returns the path relative to the project root with a `./` prefix, which is the form of the
+    /// paths in [`CheckPaths`] that files are classified against. Paths outside the root are
+    /// returned as is.
+    #[must_use]
+    pub fn relative_path(&self, path: &Path) -> PathBuf {
+        let path = path.strip_prefix(&self.root).unwrap_or(path);
+        if path.is_absolute() || path.starts_with(".") || path.starts_with("..") {
+            path.to_path_buf()
+        } else {
+            Path::new(".").join(path)
+        }
+    }
+
+    /// Runs all validators on the source of a file whose path is relative to the project root, and
+    /// returns the findings of the rules that are not `off`.
+    fn check_file_source(&self, file: &Path, src: &str) -> Vec<InvalidItem> {
+        let items = match self.parse(file, src) {
+            Ok(parsed) => {
+                VALIDATORS.iter().flat_map(|validator| validator.check(&parsed)).collect()
+            }
+            Err(err) => err.invalid_items(src, &self.config.file_config),
+        };
+        items.into_iter().filter(|item| item.severity != Severity::Off).collect()
+    }
+
+    /// Reads, parses and validates the files in parallel, skipping the ones that are ignored in
+    /// `.scopelint`. With `whole_project`, the files are all Solidity files of the project, so
+    /// the cached findings of any other file are stale.
+    fn check(&self, files: &[PathBuf], whole_project: bool) -> Result<Report> {
+        let mut report = Report::default();
+        let mut cache =
+            self.use_cache.then(|| Cache::load(&self.root, self.config.file_config.config_file()));
+
+        let mut checked = Vec::new();
+        for file in files.iter().map(|file| self.relative_path(file)).unique() {
+            if self.config.file_config.is_file_ignored(&file) {
+                report.record_skipped_file();
+            } else {
+                checked.push(file);
+            }
+        }
+
+        // Collecting keeps the results in the order of `files`, so the report does not depend on
+        // which files finish first.
+        let results: Vec<Result<(String, Vec<InvalidItem>)>> = checked
+            .par_iter()
+            .map(|file| {
+                let src = fs::read_to_string(self.root.join(file))
+                    .map_err(|err| Error::io(format!("Failed to read {}", file.display()), &err))?;
+                let content_hash = content_hash(&src);
+                if let Some(items) = cache.as_ref().and_then(|cache| cache.get(file, &content_hash))
+                {
+                    return Ok((content_hash, items.to_vec()));
+                }
+                Ok((content_hash, self.check_file_source(file, &src)))
+            })
+            .collect();
+        for (file, result) in checked.iter().zip(results) {
+            let (content_hash, items) = result?;
+            report.record_scanned_file();
+            if let Some(cache) = &mut cache {
+                cache.insert(file, content_hash, items.clone());
+            }
+            report.add_items(items);
+        }
+
+        if let Some(mut cache) = cache {
+            if whole_project {
+                cache.retain(&checked);
+            }
+            // The cache only speeds up the next check, so it is fine if it can't be written.
+            let _ = cache.write(&self.root);
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{rules::Rule, TempDir};
+
+    #[test]
+    fn test_linter() {
+        let temp_dir = TempDir::new("linter-test");
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/legacy")).unwrap();
+        fs::write(root.join(".scopelint"), "[ignore]\nfiles = [\"src/legacy/*.sol\"]\n").unwrap();
+        fs::write(root.join("src/Counter.sol"), "contract Counter {}").unwrap();
+        fs::write(root.join("src/legacy/Old.sol"), "contract Old {}").unwrap();
+
+        let linter = Linter::new(root, LinterConfig::load(root).unwrap());
+        let rules = |items: &[InvalidItem]| items.iter().map(|item| item.rule).collect::<Vec<_>>();
+
+        // Paths are relative to the root, not to the current directory.
+        let items = linter.check_source(Path::new("src/Counter.sol"), "contract Counter {}");
+        assert_eq!(rules(&items), vec![Rule::SpdxHeader]);
+        assert_eq!(items[0].file, "./src/Counter.sol");
+        let items = linter.check_source(&root.join("src/Broken.sol"), "contract {");
+        assert!(!items.is_empty() && items.iter().all(|item| item.rule == Rule::ParseError));
+        assert!(linter.check_source(Path::new("src/legacy/Old.sol"), "contract Old {}").is_empty());
+
+        let report = linter.check_project().unwrap();
+        assert_eq!(rules(report.items()), vec![Rule::SpdxHeader]);
+        let summary = report.summary();
+        assert_eq!((summary.files_scanned, summary.files_skipped), (1, 1));
+        assert!(!root.join(crate::check::cache::CACHE_DIR).exists());
+
+        assert!(linter.check_files(&[PathBuf::from("src/Missing.sol")]).is_err());
+
+        // Findings of rules that are turned off are never returned.
+        fs::write(root.join(".scopelint"), "[rules]\nsrc = \"off\"\n").unwrap();
+        let linter = Linter::new(root, LinterConfig::load(root).unwrap());
+        assert!(linter
+            .check_source(Path::new("src/Counter.sol"), "contract Counter {}")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_check_files_order() {
+        let temp_dir = TempDir::new("linter-order-test");
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        let mut files: Vec<PathBuf> =
+            ["src/C.sol", "src/A.sol", "src/B.sol"].into_iter().map(PathBuf::from).collect();
+        for file in &files {
+            fs::write(root.join(file), "contract Counter {}").unwrap();
+        }
+        // Invalid UTF-8 can't be read as source, even when running as root.
+        fs::write(root.join("src/Unreadable.sol"), [0xff, 0xfe]).unwrap();
+
+        let linter = Linter::new(root, LinterConfig::load(root).unwrap());
+        let file_names =
+            |report: &Report| report.items().iter().map(|item| item.file.clone()).collect_vec();
+
+        // Findings are in the order the files are given, however the files are scheduled.
+        let expected = vec!["./src/C.sol", "./src/A.sol", "./src/B.sol"];
+        for _ in 0..10 {
+            assert_eq!(file_names(&linter.check_files(&files).unwrap()), expected);
+        }
+
+        files.insert(1, PathBuf::from("src/Unreadable.sol"));
+        let err = linter.check_files(&files).unwrap_err();
+        assert!(matches!(err, Error::Io(_)), "{err:?}");
+        assert!(err.to_string().contains("Failed to read ./src/Unreadable.sol"), "{err}");
+    }
+}
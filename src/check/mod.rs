@@ -3,153 +3,76 @@ use crate::{
         comments::Comments,
         inline_config::{InlineConfig, InvalidInlineConfigItem},
     },
+    error::{Error, Result},
     foundry_config::CheckPaths,
 };
-use colored::Colorize;
 use itertools::Itertools;
-use solang_parser::pt::{Loc, SourceUnit};
+use solang_parser::{
+    diagnostics::Diagnostic,
+    pt::{Loc, SourceUnit},
+};
 use std::{
-    collections::HashSet,
-    error::Error,
     ffi::OsStr,
-    fs,
+    fmt, fs,
     path::{Path, PathBuf},
 };
 use walkdir::WalkDir;
 
+#[cfg(feature = "cli")]
+pub use commands::{explain, run, run_fix, CheckOptions, RuleFilter};
+
+/// Contains the baseline file used to only fail on new findings.
+pub mod baseline;
+
+/// Contains the cache of the findings of each file between runs.
+pub mod cache;
+
+/// Contains the `check`, `fix` and `explain` commands.
+#[cfg(feature = "cli")]
+mod commands;
+
+/// Contains the lines changed relative to a git ref, used to only report findings on changed code.
+pub mod changed_lines;
+
 /// Contains all the types and methods to parse comments.
 pub mod comments;
 
 /// Contains all the types and methods to define and parse inline config items.
 pub mod inline_config;
 
+/// Contains the [`linter::Linter`] that checks a project when scopelint is used as a library.
+pub mod linter;
+
 /// Contains configuration file parser for `.scopelint` file.
 pub mod file_config;
 
 /// Contains all the types and methods to generate a report of all the invalid items found.
 pub mod report;
 
+/// Contains the stable codes and explanations of the rules checked by the validators.
+pub mod rules;
+
 /// Contains helper methods, traits, etc. used by the validators and report generation.
 pub mod utils;
 
 /// Contains all the validators to ensure Solidity files follow conventions and best practices.
 pub mod validators;
 
-/// Validates the code formatting, and print details on any conventions that are not being followed.
-/// # Errors
-/// Returns an error if the formatting or convention validations fail.
-pub fn run(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
-    // We run the formatting check separate to just indicate whether or not the user needs to format
-    // the codebase, whereas the other validators return granular information about what to fix
-    // since they currently can't be fixed automatically.
-    let valid_names = validate_conventions();
-    let valid_fmt = validators::formatting::validate(taplo_opts);
-
-    if valid_names.is_ok() && valid_fmt.is_ok() {
-        Ok(())
-    } else {
-        Err("One or more checks failed, review above output".into())
-    }
-}
-
-/// Applies safe fixes (e.g. remove unused imports), then runs check.
-///
-/// # Errors
-///
-/// Returns an error if fixes could not be applied or if convention checks still fail after
-/// fixing.
-pub fn run_fix(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
-    let path_config = CheckPaths::load();
-    let results = validate(&path_config)?;
-
-    let fixable_imports: Vec<&utils::InvalidItem> = results
-        .items()
-        .iter()
-        .filter(|item| {
-            item.kind == utils::ValidatorKind::Import && !item.is_disabled && !item.is_ignored
-        })
-        .collect();
-
-    if fixable_imports.is_empty() {
-        // No fixable import issues; run normal check and return its result.
-        let valid_names = validate_conventions();
-        let valid_fmt = validators::formatting::validate(taplo_opts);
-        if valid_names.is_ok() && valid_fmt.is_ok() {
-            return Ok(());
-        }
-        return Err("One or more checks failed, review above output".into());
-    }
-
-    let file_config = file_config::FileConfig::load();
-
-    // Group fixable import items by file and collect symbol names to remove.
-    let by_file: std::collections::HashMap<&str, HashSet<String>> = fixable_imports
-        .iter()
-        .map(|item| {
-            let symbol = extract_unused_import_symbol(&item.text);
-            (item.file.as_str(), symbol)
-        })
-        .fold(std::collections::HashMap::new(), |mut acc, (file, symbol)| {
-            acc.entry(file).or_default().insert(symbol);
-            acc
-        });
-
-    let mut fixed_count = 0_usize;
-    for (file_path, symbols) in &by_file {
-        let path = Path::new(file_path);
-        if !path.exists() {
-            continue;
-        }
-        let mut parsed = parse(path)?;
-        parsed.file_config = file_config.clone();
-        parsed.path_config = path_config.clone();
-
-        if let Some(new_src) = validators::unused_imports::fix_source(&parsed, Some(symbols)) {
-            fs::write(path, new_src)?;
-            fixed_count += 1;
-        }
-    }
-
-    if fixed_count > 0 {
-        eprintln!("{}: Fixed unused imports in {} file(s)", "info".bold().green(), fixed_count);
-    }
-
-    // Re-run check and report any remaining issues.
-    let valid_names = validate_conventions();
-    let valid_fmt = validators::formatting::validate(taplo_opts);
-    if valid_names.is_ok() && valid_fmt.is_ok() {
-        Ok(())
-    } else {
-        Err("One or more checks failed, review above output".into())
-    }
-}
-
-/// Extracts the symbol name from an "Unused import: '`SymbolName`'" message.
-fn extract_unused_import_symbol(text: &str) -> String {
-    const PREFIX: &str = "Unused import: '";
-    const SUFFIX: char = '\'';
-    if let Some(stripped) = text.strip_prefix(PREFIX) {
-        if let Some(symbol) = stripped.strip_suffix(SUFFIX) {
-            return symbol.to_string();
-        }
-    }
-    text.to_string()
-}
+/// Contains the watch mode that re-runs the check when files change.
+#[cfg(feature = "cli")]
+pub mod watch;
 
 // =============================
 // ======== Validations ========
 // =============================
 
-fn validate_conventions() -> Result<(), Box<dyn Error>> {
-    let path_config = CheckPaths::load();
-    let results = validate(&path_config)?;
-
-    if !results.is_valid() {
-        eprint!("{results}");
-        eprintln!("{}: Convention checks failed, see details above", "error".bold().red());
-        return Err("Invalid names found".into());
-    }
-    Ok(())
+/// Prints an error that is not a finding, e.g. a baseline that cannot be read, since the caller
+/// only reports that the checks failed.
+#[cfg(any(feature = "fmt", feature = "spec"))]
+pub(crate) fn log_error(err: Error) -> Error {
+    use colored::Colorize;
+    eprintln!("{}: {err}", "error".bold().red());
+    err
 }
 
 /// Result of parsing the source code. This is the same struct used in forge's fmt module.
@@ -178,13 +101,20 @@ pub struct Parsed {
 /// # Errors
 ///
 /// Returns an error if the file cannot be read or its source code cannot be parsed.
-pub fn parse(file: &Path) -> Result<Parsed, Box<dyn Error>> {
-    let src = &fs::read_to_string(file)?;
+pub fn parse(file: &Path) -> Result<Parsed> {
+    let src = &fs::read_to_string(file)
+        .map_err(|err| Error::io(format!("Failed to read {}", file.display()), &err))?;
+    Ok(parse_source(file, src)?)
+}
 
-    let (pt, comments) = crate::parser::parse_solidity(src, 0).map_err(|d| {
-        eprintln!("{d:?}");
-        "Failed to parse file".to_string()
-    })?;
+/// Parses the given source code of `file` and returns a [`Parsed`] struct.
+///
+/// # Errors
+///
+/// Returns the parser diagnostics if the source code cannot be parsed.
+pub fn parse_source(file: &Path, src: &str) -> Result<Parsed, ParseError> {
+    let (pt, comments) = crate::parser::parse_solidity(src, 0)
+        .map_err(|diagnostics| ParseError { file: file.to_owned(), diagnostics })?;
 
     let comments = Comments::new(comments, src);
     let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
@@ -196,7 +126,7 @@ pub fn parse(file: &Path) -> Result<Parsed, Box<dyn Error>> {
 
     Ok(Parsed {
         file: file.to_owned(),
-        src: src.clone(),
+        src: src.to_string(),
         pt,
         comments,
         inline_config,
@@ -206,65 +136,98 @@ pub fn parse(file: &Path) -> Result<Parsed, Box<dyn Error>> {
     })
 }
 
-// Core validation method that walks the directory and validates all Solidity files.
-fn validate(path_config: &CheckPaths) -> Result<report::Report, Box<dyn Error>> {
-    let mut results = report::Report::default();
-    let file_config = file_config::FileConfig::load();
+/// The error returned when a file cannot be parsed.
+#[derive(Debug)]
+pub struct ParseError {
+    /// Path to the file.
+    pub file: PathBuf,
+    /// The errors reported by the parser.
+    pub diagnostics: Vec<Diagnostic>,
+}
 
-    for path in path_config.as_array() {
-        // Skip if the directory doesn't exist (e.g., script folder may not be created yet).
-        let path_buf = Path::new(path);
-        if !path_buf.exists() || !path_buf.is_dir() {
-            continue;
-        }
+impl ParseError {
+    /// Returns an invalid item for each error reported by the parser.
+    #[must_use]
+    pub fn invalid_items(
+        &self,
+        src: &str,
+        file_config: &file_config::FileConfig,
+    ) -> Vec<utils::InvalidItem> {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| {
+                utils::InvalidItem::new_unparsed(
+                    rules::Rule::ParseError,
+                    &self.file,
+                    src,
+                    file_config,
+                    diagnostic.loc,
+                    diagnostic.message.clone(),
+                )
+            })
+            .collect()
+    }
+}
 
-        for result in WalkDir::new(path) {
-            let dent = match result {
-                Ok(dent) => dent,
-                Err(err) => {
-                    eprintln!("{err}");
-                    continue;
-                }
-            };
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to parse {}", self.file.display())?;
+        if let Some(diagnostic) = self.diagnostics.first() {
+            write!(f, ": {}", diagnostic.message)?;
+        }
+        Ok(())
+    }
+}
 
-            if !dent.file_type().is_file() || dent.path().extension() != Some(OsStr::new("sol")) {
-                continue;
-            }
+impl std::error::Error for ParseError {}
 
-            let file_path = dent.path();
+/// Returns the Solidity files in the directory, in the order they are walked. A directory that
+/// doesn't exist has no files.
+fn walk_solidity_files(dir: &Path) -> Result<Vec<PathBuf>, walkdir::Error> {
+    // Skip if the directory doesn't exist (e.g., script folder may not be created yet).
+    if !dir.exists() || !dir.is_dir() {
+        return Ok(Vec::new());
+    }
 
-            // Check if file should be ignored entirely
-            if file_config.is_file_ignored(file_path) {
-                continue;
-            }
+    let mut files = Vec::new();
+    for result in WalkDir::new(dir) {
+        let dent = result?;
+        if dent.file_type().is_file() && dent.path().extension() == Some(OsStr::new("sol")) {
+            files.push(dent.into_path());
+        }
+    }
+    Ok(files)
+}
 
-            // Get the parse tree (pt) of the file and extract inline configs.
-            let mut parsed = parse(file_path)?;
-            // Attach file config and path config to parsed struct
-            parsed.file_config = file_config.clone();
-            parsed.path_config = path_config.clone();
+/// A directory for the files of a unit test, which is removed when dropped, so a failing
+/// assertion does not leave it behind.
+#[cfg(test)]
+pub(crate) struct TempDir(PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    /// Creates an empty directory with a name unique to this test run.
+    pub(crate) fn new(name: &str) -> Self {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "scopelint-{name}-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
 
-            // If there are any invalid inline config items, add them to the results.
-            for invalid_item in &parsed.invalid_inline_config_items {
-                results.add_item(utils::InvalidItem::new(
-                    utils::ValidatorKind::Directive,
-                    &parsed,
-                    invalid_item.0,
-                    invalid_item.1.to_string(),
-                ));
-            }
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+}
 
-            // Run all checks.
-            results.add_items(validators::test_names::validate(&parsed));
-            results.add_items(validators::src_names_internal::validate(&parsed));
-            results.add_items(validators::script_has_public_run_method::validate(&parsed));
-            results.add_items(validators::constant_names::validate(&parsed));
-            results.add_items(validators::src_spdx_header::validate(&parsed));
-            results.add_items(validators::variable_names::validate(&parsed));
-            results.add_items(validators::error_prefix::validate(&parsed));
-            results.add_items(validators::eip712_typehash::validate(&parsed));
-            results.add_items(validators::unused_imports::validate(&parsed));
-        }
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
     }
-    Ok(results)
 }
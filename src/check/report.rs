@@ -1,44 +1,599 @@
-use super::utils::InvalidItem;
+use super::{
+    baseline::{Baseline, BaselineEntry},
+    rules::Rule,
+    utils::{Colorize, InvalidItem, Severity, ValidatorKind},
+    validators,
+};
 use itertools::Itertools;
-use std::fmt;
+use serde::Serialize;
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
+
+/// The SARIF version emitted by [`Report::to_sarif`].
+const SARIF_VERSION: &str = "2.1.0";
+
+/// The JSON schema of the SARIF version emitted by [`Report::to_sarif`].
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The format used to print a [`Report`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum OutputFormat {
+    /// Human-readable text, one finding per line.
+    #[default]
+    Text,
+    /// A JSON document containing every finding and a summary.
+    Json,
+    /// A SARIF 2.1.0 log, for code-scanning UIs.
+    Sarif,
+    /// GitHub Actions workflow commands, so the runner annotates the diff.
+    Github,
+    /// A `JUnit` XML report, for CI systems that aggregate test reports.
+    Junit,
+}
 
 /// A collection of invalid items to generate a report from.
-#[derive(Default)]
+#[derive(Debug, Default)]
 pub struct Report {
     /// A list of invalid items.
     invalid_items: Vec<InvalidItem>,
+    /// Baseline entries that no longer match any invalid item, or `None` if no baseline was
+    /// applied.
+    fixed_baseline_entries: Option<Vec<BaselineEntry>>,
+    /// Number of files that were validated.
+    files_scanned: usize,
+    /// Number of files that were skipped because they are ignored in `.scopelint`.
+    files_skipped: usize,
+}
+
+/// Totals of the findings in a [`Report`], printed at the end of a `check` run.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Number of reported findings per validator kind, in the order of [`ValidatorKind::all`].
+    /// Kinds without findings are omitted.
+    pub by_kind: Vec<(ValidatorKind, usize)>,
+    /// Number of reported findings per file, sorted by file. Files without findings are omitted.
+    pub by_file: Vec<(String, usize)>,
+    /// Number of findings suppressed by inline directives.
+    pub suppressed_inline: usize,
+    /// Number of findings suppressed by overrides in `.scopelint`.
+    pub suppressed_config: usize,
+    /// Number of findings that are recorded in the baseline.
+    pub baselined: usize,
+    /// Number of files that were validated.
+    pub files_scanned: usize,
+    /// Number of files that were skipped because they are ignored in `.scopelint`.
+    pub files_skipped: usize,
+}
+
+impl Summary {
+    /// Returns the total number of reported findings.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.by_kind.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: {} finding(s), {} file(s) scanned, {} skipped",
+            "Summary".bold(),
+            self.total(),
+            self.files_scanned,
+            self.files_skipped
+        )?;
+        if !self.by_kind.is_empty() {
+            writeln!(f, "  By rule:")?;
+            let width = self.by_kind.iter().map(|(kind, _)| kind.name().len()).max().unwrap_or(0);
+            for (kind, count) in &self.by_kind {
+                writeln!(f, "    {:<width$}  {count}", kind.name())?;
+            }
+        }
+        if !self.by_file.is_empty() {
+            writeln!(f, "  By file:")?;
+            let width = self.by_file.iter().map(|(file, _)| file.len()).max().unwrap_or(0);
+            for (file, count) in &self.by_file {
+                writeln!(f, "    {file:<width$}  {count}")?;
+            }
+        }
+        writeln!(
+            f,
+            "  Suppressed: {} by inline directives, {} by .scopelint overrides",
+            self.suppressed_inline, self.suppressed_config
+        )?;
+        if self.baselined > 0 {
+            writeln!(f, "  Baselined: {}", self.baselined)?;
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for Report {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
-        self.invalid_items
-            .iter()
-            .filter(|item| !item.is_disabled && !item.is_ignored)
-            .sorted_unstable()
-            .try_for_each(|item| writeln!(f, "{}", item.description()))
+        f.write_str(&self.to_text(false))
     }
 }
 
 impl Report {
-    /// Extends the report with the invalid item.
+    /// Extends the report with the invalid item, unless its rule is turned off.
     pub fn add_item(&mut self, item: InvalidItem) {
-        self.invalid_items.push(item);
+        if item.severity != Severity::Off {
+            self.invalid_items.push(item);
+        }
     }
 
-    /// Extends the report with a list of invalid items.
+    /// Extends the report with a list of invalid items, skipping those whose rule is turned off.
     pub fn add_items(&mut self, items: Vec<InvalidItem>) {
-        self.invalid_items.extend(items);
+        self.invalid_items.extend(items.into_iter().filter(|item| item.severity != Severity::Off));
     }
 
-    /// Returns all invalid items (including ignored/disabled).
+    /// Records that a file was validated.
+    pub const fn record_scanned_file(&mut self) {
+        self.files_scanned += 1;
+    }
+
+    /// Records that a file was skipped because it is ignored in `.scopelint`.
+    pub const fn record_skipped_file(&mut self) {
+        self.files_skipped += 1;
+    }
+
+    /// Returns the totals of the findings, grouped by validator kind and by file.
+    #[must_use]
+    pub fn summary(&self) -> Summary {
+        let reported: Vec<&InvalidItem> =
+            self.invalid_items.iter().filter(|item| !item.is_suppressed()).collect();
+        let by_kind = ValidatorKind::all()
+            .into_iter()
+            .map(|kind| (kind, reported.iter().filter(|item| item.kind == kind).count()))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        let by_file =
+            reported.iter().counts_by(|item| item.file.clone()).into_iter().sorted().collect();
+        let suppressed_config =
+            self.invalid_items.iter().filter(|item| item.is_ignored_by_config).count();
+        let suppressed_inline = self
+            .invalid_items
+            .iter()
+            .filter(|item| (item.is_disabled || item.is_ignored) && !item.is_ignored_by_config)
+            .count();
+
+        Summary {
+            by_kind,
+            by_file,
+            suppressed_inline,
+            suppressed_config,
+            baselined: self.invalid_items.iter().filter(|item| item.is_baselined).count(),
+            files_scanned: self.files_scanned,
+            files_skipped: self.files_skipped,
+        }
+    }
+
+    /// Keeps only the invalid items for which the predicate returns `true`.
+    pub fn retain(&mut self, predicate: impl FnMut(&InvalidItem) -> bool) {
+        self.invalid_items.retain(predicate);
+    }
+
+    /// Marks the invalid items recorded in the baseline as baselined, so they no longer make the
+    /// check fail, and keeps track of the baseline entries that have been fixed.
+    pub fn apply_baseline(&mut self, baseline: &Baseline) {
+        self.fixed_baseline_entries = Some(baseline.apply(&mut self.invalid_items));
+    }
+
+    /// Returns the baseline entries that no longer match any invalid item. This is empty if no
+    /// baseline was applied.
+    #[must_use]
+    pub fn fixed_baseline_entries(&self) -> &[BaselineEntry] {
+        self.fixed_baseline_entries.as_deref().unwrap_or_default()
+    }
+
+    /// Returns all invalid items (including ignored/disabled/baselined).
     #[must_use]
     pub fn items(&self) -> &[InvalidItem] {
         &self.invalid_items
     }
 
-    /// Returns true if no issues were found.
+    /// Returns true if no issues that make the check fail were found. Warnings are allowed.
     #[must_use]
     pub fn is_valid(&self) -> bool {
-        !self.invalid_items.iter().any(|item| !item.is_disabled && !item.is_ignored)
+        !self.invalid_items.iter().any(InvalidItem::is_error)
+    }
+
+    /// Returns the number of reported invalid items whose rule is set to `warn`.
+    #[must_use]
+    pub fn warning_count(&self) -> usize {
+        self.invalid_items.iter().filter(|item| item.is_warning()).count()
+    }
+
+    /// Formats every invalid item that is not disabled, ignored or baselined as human-readable
+    /// text, one finding per line, with warnings prefixed by `warning: `. When `show_snippets`
+    /// is true, each finding is followed by the offending source code with the item underlined.
+    #[must_use]
+    pub fn to_text(&self, show_snippets: bool) -> String {
+        self.invalid_items.iter().filter(|item| !item.is_suppressed()).sorted_unstable().fold(
+            String::new(),
+            |mut out, item| {
+                if item.severity == Severity::Warn {
+                    let _ = writeln!(out, "{}: {}", "warning".bold().yellow(), item.description());
+                } else {
+                    let _ = writeln!(out, "{}", item.description());
+                }
+                if show_snippets {
+                    let _ = writeln!(out, "{}", item.snippet());
+                }
+                out
+            },
+        )
+    }
+
+    /// Serializes every invalid item, including disabled, ignored and baselined ones, along with
+    /// the fixed baseline entries and a summary of the results into a JSON document.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the report cannot be serialized, which should not happen since it only contains
+    /// strings, numbers and booleans.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let findings: Vec<JsonFinding<'_>> =
+            self.invalid_items.iter().sorted_unstable().map(JsonFinding::from).collect();
+        let Summary {
+            by_kind,
+            by_file,
+            suppressed_inline,
+            suppressed_config,
+            files_scanned,
+            files_skipped,
+            ..
+        } = self.summary();
+        let summary = JsonSummary {
+            total: findings.len(),
+            failed: self.invalid_items.iter().filter(|item| item.is_error()).count(),
+            warnings: self.warning_count(),
+            disabled: findings.iter().filter(|item| item.is_disabled).count(),
+            ignored: findings.iter().filter(|item| item.is_ignored).count(),
+            baselined: findings.iter().filter(|item| item.is_baselined).count(),
+            valid: self.is_valid(),
+            by_kind: by_kind.into_iter().map(|(kind, count)| (kind.name(), count)).collect(),
+            by_file: by_file.into_iter().collect(),
+            suppressed_inline,
+            suppressed_config,
+            files_scanned,
+            files_skipped,
+        };
+        let fixed_baseline = self.fixed_baseline_entries();
+        serde_json::to_string_pretty(&JsonReport { findings, fixed_baseline, summary })
+            .expect("report is always serializable")
+    }
+
+    /// Serializes the report into a SARIF 2.1.0 log with a single run. Each [`Rule`] is a SARIF
+    /// rule identified by its code, and disabled or ignored items are kept as results with an
+    /// `inSource` suppression, justified by the reason given on the directive, or an `external`
+    /// one if they are ignored in `.scopelint`. Items whose rule is set to `warn` have the
+    /// `warning` level. When a baseline was applied, each result has a `baselineState` of
+    /// `unchanged` if it is in the baseline or `new` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the log cannot be serialized, which should not happen since it only contains
+    /// strings, numbers and booleans.
+    #[must_use]
+    pub fn to_sarif(&self) -> String {
+        let all_rules = Rule::all();
+        let rules: Vec<serde_json::Value> = all_rules
+            .iter()
+            .map(|rule| {
+                json!({
+                    "id": rule.code(),
+                    "name": rule.name(),
+                    "shortDescription": { "text": rule.summary() },
+                    "fullDescription": { "text": rule.rationale() },
+                    "help": { "text": rule.explain() },
+                    "defaultConfiguration": {
+                        "level": sarif_level(validators::for_kind(rule.kind()).default_severity()),
+                    },
+                    "properties": { "tags": [rule.kind().name()] },
+                })
+            })
+            .collect();
+
+        let results: Vec<serde_json::Value> = self
+            .invalid_items
+            .iter()
+            .sorted_unstable()
+            .map(|item| {
+                let mut result = json!({
+                    "ruleId": item.rule.code(),
+                    "ruleIndex": all_rules.iter().position(|rule| *rule == item.rule),
+                    "level": sarif_level(item.severity),
+                    "message": { "text": item.description() },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": item.file.strip_prefix("./").unwrap_or(&item.file),
+                            },
+                            "region": {
+                                "startLine": item.line,
+                                "startColumn": item.column,
+                                "endLine": item.end_line,
+                                "endColumn": item.end_column,
+                            },
+                        },
+                    }],
+                });
+                if item.is_ignored_by_config {
+                    result["suppressions"] = json!([{ "kind": "external" }]);
+                } else if item.is_disabled || item.is_ignored {
+                    result["suppressions"] = json!([{ "kind": "inSource" }]);
+                    if let Some(reason) = &item.ignore_reason {
+                        result["suppressions"][0]["justification"] = json!(reason);
+                    }
+                }
+                if self.fixed_baseline_entries.is_some() {
+                    result["baselineState"] =
+                        json!(if item.is_baselined { "unchanged" } else { "new" });
+                }
+                result
+            })
+            .collect();
+
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        });
+        serde_json::to_string_pretty(&log).expect("SARIF log is always serializable")
+    }
+
+    /// Formats every invalid item that is not disabled, ignored or baselined as a GitHub Actions
+    /// `::error` workflow command, or `::warning` if its rule is set to `warn`, one per line.
+    #[must_use]
+    pub fn to_github(&self) -> String {
+        self.invalid_items
+            .iter()
+            .filter(|item| !item.is_suppressed())
+            .sorted_unstable()
+            .map(|item| {
+                let file = item.file.strip_prefix("./").unwrap_or(&item.file);
+                let title = format!("scopelint ({})", item.kind.name());
+                let location = AnnotationLocation {
+                    line: item.line,
+                    column: item.column,
+                    end_line: item.end_line,
+                    end_column: item.end_column,
+                };
+                github_annotation(
+                    item.severity,
+                    Some(file),
+                    Some(&location),
+                    &title,
+                    &item.description(),
+                ) + "\n"
+            })
+            .collect()
     }
+
+    /// Serializes the report into a `JUnit` XML document with one `testsuite` per
+    /// [`ValidatorKind`]. Each invalid item is a `testcase` that fails, or is skipped when it is
+    /// disabled, ignored or baselined. Warnings are passing `testcase`s with the finding in
+    /// `system-out`. Validator kinds without any findings contain a single passing `testcase`.
+    #[must_use]
+    pub fn to_junit(&self) -> String {
+        let mut suites = String::new();
+        let (mut total_tests, mut total_failures, mut total_skipped) = (0, 0, 0);
+
+        for kind in &ValidatorKind::all() {
+            let items: Vec<&InvalidItem> = self
+                .invalid_items
+                .iter()
+                .filter(|item| item.kind == *kind)
+                .sorted_unstable()
+                .collect();
+            let classname = format!("scopelint.{}", kind.name());
+            let skipped = items.iter().filter(|item| item.is_suppressed()).count();
+            let failures = items.iter().filter(|item| item.is_error()).count();
+            let tests = items.len().max(1);
+            total_tests += tests;
+            total_failures += failures;
+            total_skipped += skipped;
+
+            let mut cases = String::new();
+            for item in &items {
+                let name = xml_escape(&format!("{}:{}: {}", item.file, item.line, item.text));
+                let _ = writeln!(cases, r#"    <testcase classname="{classname}" name="{name}">"#);
+                if item.is_suppressed() {
+                    let _ = writeln!(cases, "      <skipped/>");
+                } else if item.is_warning() {
+                    let _ = writeln!(
+                        cases,
+                        "      <system-out>warning: {}</system-out>",
+                        xml_escape(&item.description())
+                    );
+                } else {
+                    let _ = writeln!(
+                        cases,
+                        r#"      <failure type="{}" message="{}">{}</failure>"#,
+                        kind.name(),
+                        xml_escape(&item.text),
+                        xml_escape(&item.description())
+                    );
+                }
+                let _ = writeln!(cases, "    </testcase>");
+            }
+            if items.is_empty() {
+                let _ = writeln!(
+                    cases,
+                    r#"    <testcase classname="{classname}" name="{}"/>"#,
+                    kind.name()
+                );
+            }
+
+            let _ = writeln!(
+                suites,
+                r#"  <testsuite name="{classname}" tests="{tests}" failures="{failures}" errors="0" skipped="{skipped}">"#
+            );
+            suites.push_str(&cases);
+            let _ = writeln!(suites, "  </testsuite>");
+        }
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"scopelint\" \
+             tests=\"{total_tests}\" failures=\"{total_failures}\" errors=\"0\" \
+             skipped=\"{total_skipped}\">\n{suites}</testsuites>\n"
+        )
+    }
+}
+
+/// Escapes the characters that are not allowed in XML attribute values and text.
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// The position of a GitHub Actions annotation within a file.
+pub(crate) struct AnnotationLocation {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+    pub(crate) end_line: usize,
+    pub(crate) end_column: usize,
+}
+
+/// Returns the SARIF `level` of a result with the given severity.
+const fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warn => "warning",
+        Severity::Error | Severity::Off => "error",
+    }
+}
+
+/// Formats a GitHub Actions `::error` or `::warning` workflow command, depending on the severity.
+/// Properties and the message are escaped as described in
+/// <https://github.com/actions/toolkit/blob/main/packages/core/src/command.ts>.
+pub(crate) fn github_annotation(
+    severity: Severity,
+    file: Option<&str>,
+    location: Option<&AnnotationLocation>,
+    title: &str,
+    message: &str,
+) -> String {
+    let mut properties = Vec::new();
+    if let Some(file) = file {
+        properties.push(format!("file={}", escape_github_property(file)));
+    }
+    if let Some(location) = location {
+        properties.push(format!("line={}", location.line));
+        properties.push(format!("endLine={}", location.end_line));
+        // GitHub only supports columns for annotations on a single line.
+        if location.line == location.end_line {
+            properties.push(format!("col={}", location.column));
+            properties.push(format!("endColumn={}", location.end_column));
+        }
+    }
+    properties.push(format!("title={}", escape_github_property(title)));
+    let command = if severity == Severity::Warn { "warning" } else { "error" };
+    format!("::{command} {}::{}", properties.join(","), escape_github_data(message))
+}
+
+fn escape_github_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_github_property(s: &str) -> String {
+    escape_github_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// The JSON representation of a [`Report`].
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    findings: Vec<JsonFinding<'a>>,
+    fixed_baseline: &'a [BaselineEntry],
+    summary: JsonSummary,
+}
+
+/// The JSON representation of a single [`InvalidItem`].
+#[derive(Serialize)]
+struct JsonFinding<'a> {
+    code: &'static str,
+    kind: &'static str,
+    file: &'a str,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    message: &'a str,
+    description: String,
+    severity: &'static str,
+    is_disabled: bool,
+    is_ignored: bool,
+    is_baselined: bool,
+    ignore_reason: Option<&'a str>,
+}
+
+impl<'a> From<&'a InvalidItem> for JsonFinding<'a> {
+    fn from(item: &'a InvalidItem) -> Self {
+        Self {
+            code: item.rule.code(),
+            kind: item.kind.name(),
+            file: &item.file,
+            line: item.line,
+            column: item.column,
+            end_line: item.end_line,
+            end_column: item.end_column,
+            message: &item.text,
+            description: item.description(),
+            severity: item.severity.name(),
+            is_disabled: item.is_disabled,
+            is_ignored: item.is_ignored,
+            is_baselined: item.is_baselined,
+            ignore_reason: item.ignore_reason.as_deref(),
+        }
+    }
+}
+
+/// Totals of the findings in a [`Report`].
+#[derive(Serialize)]
+struct JsonSummary {
+    /// Number of findings, including disabled and ignored ones.
+    total: usize,
+    /// Number of findings that cause the check to fail.
+    failed: usize,
+    /// Number of findings reported as warnings, which do not cause the check to fail.
+    warnings: usize,
+    /// Number of findings in a disabled region.
+    disabled: usize,
+    /// Number of findings that were ignored by an inline directive or the `.scopelint` file.
+    ignored: usize,
+    /// Number of findings that are recorded in the baseline.
+    baselined: usize,
+    /// Whether the check passed.
+    valid: bool,
+    /// Number of reported findings per validator kind.
+    by_kind: BTreeMap<&'static str, usize>,
+    /// Number of reported findings per file.
+    by_file: BTreeMap<String, usize>,
+    /// Number of findings suppressed by inline directives.
+    suppressed_inline: usize,
+    /// Number of findings suppressed by overrides in `.scopelint`.
+    suppressed_config: usize,
+    /// Number of files that were validated.
+    files_scanned: usize,
+    /// Number of files that were skipped because they are ignored in `.scopelint`.
+    files_skipped: usize,
 }
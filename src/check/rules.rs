@@ -0,0 +1,245 @@
+//! Stable codes for each distinct check performed by the validators, and their explanations.
+//!
+//! Each rule is described by a [`RuleInfo`](crate::check::rules::RuleInfo) declared next to the
+//! validator that reports it, see [`Validator::rules`](crate::check::validators::Validator::rules).
+//!
+//! Codes are grouped by [`ValidatorKind`](crate::check::utils::ValidatorKind): `SL1xx` for `src`,
+//! `SL2xx` for `test`, `SL3xx` for `script`, `SL4xx` for `constant`, `SL5xx` for `variable`,
+//! `SL6xx` for `error`, `SL7xx` for `eip712`, `SL8xx` for `import`, `SL9xx` for `directive` and
+//! `SL0xx` for `parse`. Codes must never be reused or reassigned, since users reference them in CI
+//! logs and code reviews.
+
+use crate::check::{
+    utils::ValidatorKind,
+    validators::{self, Validator},
+};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// A distinct check performed by a validator.
+///
+/// Variants are declared in the order of their codes, so findings sorted by rule are listed in the
+/// same order as [`Rule::all`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Rule {
+    /// Solidity files can be parsed.
+    ParseError,
+    /// Internal and private functions in source contracts are prefixed with an underscore.
+    InternalFunctionName,
+    /// Source files start with an SPDX license identifier.
+    SpdxHeader,
+    /// Test names follow the naming convention.
+    TestName,
+    /// Scripts have a public `run` method.
+    ScriptRunMethod,
+    /// Constants and immutables are in `ALL_CAPS`.
+    ConstantName,
+    /// Function parameters are prefixed with an underscore.
+    ParameterName,
+    /// Storage parameters are not prefixed with an underscore.
+    StorageParameterName,
+    /// State variables are not prefixed with an underscore.
+    StateVariableName,
+    /// Local variables are prefixed with an underscore.
+    LocalVariableName,
+    /// Local storage variables are not prefixed with an underscore.
+    StorageLocalVariableName,
+    /// Custom errors are prefixed with the contract name.
+    ErrorPrefix,
+    /// EIP-712 typehashes have as many parameters as the data encoded with them.
+    Eip712ParameterMismatch,
+    /// EIP-712 typehashes are computed from a type string.
+    Eip712MissingTypeString,
+    /// Imported symbols are used.
+    UnusedImport,
+    /// Inline `scopelint:` directives are valid.
+    InvalidDirective,
+    /// Inline ignore directives suppress at least one finding.
+    UnusedDirective,
+    /// Inline ignore directives give a reason, if required by `.scopelint`.
+    MissingIgnoreReason,
+}
+
+/// The code, name and explanation of a [`Rule`].
+#[derive(Debug)]
+pub struct RuleInfo {
+    /// The rule described.
+    pub rule: Rule,
+    /// The stable code of the rule, e.g. `SL201`.
+    pub code: &'static str,
+    /// A short, human-readable name of the rule.
+    pub name: &'static str,
+    /// A one-line summary of the rule.
+    pub summary: &'static str,
+    /// Why the rule exists.
+    pub rationale: &'static str,
+    /// An example of Solidity code that violates the rule.
+    pub bad_example: &'static str,
+    /// An example of Solidity code that follows the rule.
+    pub good_example: &'static str,
+}
+
+impl Rule {
+    /// Returns all rules reported by the validators, in the order of their codes.
+    #[must_use]
+    pub fn all() -> Vec<Self> {
+        validators::VALIDATORS
+            .iter()
+            .flat_map(|validator| validator.rules())
+            .map(|info| info.rule)
+            .sorted()
+            .collect()
+    }
+
+    /// Returns the validator that reports the rule.
+    fn validator(self) -> &'static dyn Validator {
+        validators::VALIDATORS
+            .iter()
+            .copied()
+            .find(|validator| validator.rules().iter().any(|info| info.rule == self))
+            .expect("every rule is reported by a validator")
+    }
+
+    /// Returns the description of the rule declared by the validator that reports it.
+    fn info(self) -> &'static RuleInfo {
+        self.validator()
+            .rules()
+            .iter()
+            .find(|info| info.rule == self)
+            .expect("the validator of a rule describes it")
+    }
+
+    /// Returns the stable code of the rule, e.g. `SL201`.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        self.info().code
+    }
+
+    /// Returns a short, human-readable name of the rule.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    /// Returns the validator kind the rule belongs to, which is used to ignore it.
+    #[must_use]
+    pub fn kind(&self) -> ValidatorKind {
+        self.validator().kind()
+    }
+
+    /// Returns the rule with the given code, ignoring case.
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all().into_iter().find(|rule| rule.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Returns a one-line summary of the rule.
+    #[must_use]
+    pub fn summary(&self) -> &'static str {
+        self.info().summary
+    }
+
+    /// Returns why the rule exists.
+    #[must_use]
+    pub fn rationale(&self) -> &'static str {
+        self.info().rationale
+    }
+
+    /// Returns an example of Solidity code that violates the rule.
+    #[must_use]
+    pub fn bad_example(&self) -> &'static str {
+        self.info().bad_example
+    }
+
+    /// Returns an example of Solidity code that follows the rule.
+    #[must_use]
+    pub fn good_example(&self) -> &'static str {
+        self.info().good_example
+    }
+
+    /// Returns the full explanation of the rule printed by `scopelint explain`: its summary,
+    /// rationale, examples and how to ignore it.
+    #[must_use]
+    pub fn explain(&self) -> String {
+        let mut out = format!("{} {} ({})\n\n", self.code(), self.name(), self.kind().name());
+        let _ = writeln!(out, "{}\n\n{}\n", self.summary(), self.rationale());
+        let _ = writeln!(out, "Bad:\n{}\n", indent(self.bad_example()));
+        let _ = writeln!(out, "Good:\n{}\n", indent(self.good_example()));
+
+        if self.kind() == ValidatorKind::Parse {
+            let _ = writeln!(
+                out,
+                "Inline directives cannot ignore parse errors. If the file is valid Solidity that \
+                 scopelint cannot parse, list it under `[ignore] files` in `.scopelint`, or add \
+                 this to `.scopelint` to skip unparsable files:\n{}",
+                indent("[rules]\nparse = \"off\"")
+            );
+        } else if self.kind() == ValidatorKind::UnusedDirective {
+            let _ = writeln!(
+                out,
+                "Remove the directive, or run `scopelint fix` to remove all unused directives. To \
+                 allow unused directives, add this to `.scopelint`:\n{}",
+                indent("[rules]\nunused-directive = \"off\"")
+            );
+        } else {
+            let rule = self.kind().name();
+            let _ = writeln!(
+                out,
+                "To ignore the `{rule}` rules on the next line, add this comment above it:\n{}",
+                indent(&format!("// scopelint: ignore-{rule}-next-line"))
+            );
+            let _ = writeln!(
+                out,
+                "Use `ignore-{rule}-file` to ignore them in a whole file, or list \"{rule}\" under \
+                 `[ignore.overrides]` in `.scopelint`."
+            );
+        }
+        out
+    }
+}
+
+/// Indents each line of the text by four spaces.
+fn indent(text: &str) -> String {
+    text.lines().map(|line| format!("    {line}")).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_codes_are_unique_and_grouped_by_kind() {
+        let rules = Rule::all();
+        let codes: HashSet<&str> = rules.iter().map(Rule::code).collect();
+        assert_eq!(codes.len(), rules.len());
+        for pair in rules.windows(2) {
+            assert!(pair[0] < pair[1] && pair[0].code() < pair[1].code(), "{}", pair[1].code());
+        }
+
+        for rule in rules {
+            let group = &rule.code()[2..3];
+            let expected_group = match rule.kind() {
+                ValidatorKind::Src => "1",
+                ValidatorKind::Test => "2",
+                ValidatorKind::Script => "3",
+                ValidatorKind::Constant => "4",
+                ValidatorKind::Variable => "5",
+                ValidatorKind::Error => "6",
+                ValidatorKind::Eip712 => "7",
+                ValidatorKind::Import => "8",
+                ValidatorKind::Directive | ValidatorKind::UnusedDirective => "9",
+                ValidatorKind::Parse => "0",
+            };
+            assert_eq!(group, expected_group, "{}", rule.code());
+        }
+    }
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(Rule::from_code("SL201"), Some(Rule::TestName));
+        assert_eq!(Rule::from_code("sl101"), Some(Rule::InternalFunctionName));
+        assert_eq!(Rule::from_code("SL000"), None);
+    }
+}
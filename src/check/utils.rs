@@ -3,19 +3,45 @@
 // extensions manually with `ends_with`.
 #![allow(clippy::case_sensitive_file_extension_comparisons)]
 
-use super::Parsed;
+use super::{file_config::FileConfig, rules::Rule, validators, Parsed};
 use crate::foundry_config::CheckPaths;
+#[cfg(feature = "colored")]
+pub(crate) use colored::Colorize;
+use serde::{Deserialize, Serialize};
 use solang_parser::pt::{
     FunctionAttribute, FunctionDefinition, FunctionTy, Loc, SourceUnit, Visibility,
 };
 use std::path::Path;
 
+/// Leaves text uncolored in place of `colored::Colorize` when the `colored` feature is disabled.
+#[cfg(not(feature = "colored"))]
+pub(crate) trait Colorize: std::fmt::Display {
+    fn bold(&self) -> String {
+        self.to_string()
+    }
+
+    fn red(&self) -> String {
+        self.to_string()
+    }
+
+    fn yellow(&self) -> String {
+        self.to_string()
+    }
+
+    fn blue(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(not(feature = "colored"))]
+impl<T: std::fmt::Display + ?Sized> Colorize for T {}
+
 // =======================================
 // ======== For validator methods ========
 // ===============================-=======
 
 /// The type of validator that found the invalid item.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize)]
 pub enum ValidatorKind {
     /// A constant or immutable variable.
     Constant,
@@ -35,77 +61,199 @@ pub enum ValidatorKind {
     Eip712,
     /// An unused import.
     Import,
+    /// A `// scopelint: ignore-*` comment that does not suppress any finding.
+    UnusedDirective,
+    /// A file that cannot be parsed.
+    Parse,
+}
+
+impl ValidatorKind {
+    /// Returns all validator kinds, in the order they are reported.
+    #[must_use]
+    pub fn all() -> Vec<Self> {
+        validators::VALIDATORS.iter().map(|validator| validator.kind()).sorted().dedup().collect()
+    }
+
+    /// Returns the rule name of the validator kind, as used in `.scopelint` files, inline
+    /// directives and machine-readable output.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        validators::for_kind(*self).id()
+    }
+}
+
+/// How findings of a rule are treated, as configured in the `[rules]` section of `.scopelint`.
+#[derive(
+    Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize,
+)]
+pub enum Severity {
+    /// Findings are reported and make the check fail.
+    #[default]
+    Error,
+    /// Findings are reported but do not make the check fail.
+    Warn,
+    /// Findings are not reported.
+    Off,
+}
+
+impl Severity {
+    /// Parses a severity level as written in `.scopelint` (`error`, `warn` or `off`).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "error" => Some(Self::Error),
+            "warn" | "warning" => Some(Self::Warn),
+            "off" => Some(Self::Off),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the severity level, as used in machine-readable output.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warning",
+            Self::Off => "off",
+        }
+    }
 }
 
 /// A single invalid item found by a validator.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // The flags are independent ways to suppress an item.
 pub struct InvalidItem {
     pub kind: ValidatorKind,
-    pub file: String,      // File name.
-    pub text: String,      // Details to show about the invalid item.
-    pub line: usize,       // Line number.
-    pub is_disabled: bool, // Whether the invalid item is in a disabled region.
-    pub is_ignored: bool,  // Whether the invalid item is in an ignored region.
+    pub file: String,                  // File name.
+    pub text: String,                  // Details to show about the invalid item.
+    pub line: usize,                   // Line number.
+    pub is_disabled: bool,             // Whether the invalid item is in a disabled region.
+    pub is_ignored: bool,              // Whether the invalid item is in an ignored region.
+    pub column: usize,                 // Column of the start of the invalid item, 1-based.
+    pub end_line: usize,               // Line number of the end of the invalid item.
+    pub end_column: usize,             // Column just past the end of the invalid item, 1-based.
+    pub source_line: String,           // Source code of the line the invalid item starts on.
+    pub severity: Severity,            // Severity configured for the rule in `.scopelint`.
+    pub is_baselined: bool,            // Whether the invalid item is recorded in the baseline.
+    pub rule: Rule,                    // The distinct check that found the invalid item.
+    pub is_ignored_by_config: bool,    // Whether the invalid item is ignored by `.scopelint`.
+    pub ignore_reason: Option<String>, // Reason given on the inline directive ignoring the item.
 }
 
 impl InvalidItem {
     #[must_use]
     /// Creates a new `InvalidItem`.
-    pub fn new(kind: ValidatorKind, parsed: &Parsed, loc: Loc, text: String) -> Self {
+    pub fn new(rule: Rule, parsed: &Parsed, loc: Loc, text: String) -> Self {
+        let kind = rule.kind();
         let Parsed { file, src, inline_config, file_config, .. } = parsed;
-        let line = offset_to_line(src, loc.start());
-        let is_disabled = inline_config.is_disabled(loc);
-        // Check both generic ignore and rule-specific ignore (from inline comments)
-        let is_ignored_inline =
-            inline_config.is_ignored(loc) || inline_config.is_rule_ignored(loc, &kind);
+        let mut item = Self::new_unparsed(rule, file, src, file_config, loc, text);
+        item.is_disabled = inline_config.is_disabled(loc);
+        // Check both generic ignore and rule-specific ignore (from inline comments). Both are
+        // evaluated, so every directive that applies is marked as used.
+        let is_ignored = inline_config.is_ignored(loc);
+        let is_rule_ignored = inline_config.is_rule_ignored(loc, &kind);
+        if is_ignored || is_rule_ignored {
+            item.is_ignored = true;
+            item.ignore_reason = inline_config.ignore_reason(loc, &kind).map(str::to_string);
+        }
+        item
+    }
+
+    #[must_use]
+    /// Creates a new `InvalidItem` in a file that could not be parsed. Inline directives do not
+    /// apply, since they are only known once the file is parsed, but `.scopelint` does.
+    pub fn new_unparsed(
+        rule: Rule,
+        file: &Path,
+        src: &str,
+        file_config: &FileConfig,
+        loc: Loc,
+        text: String,
+    ) -> Self {
+        let kind = rule.kind();
+        let (line, column) = offset_to_line_column(src, loc.start());
+        let (end_line, end_column) = offset_to_line_column(src, loc.end());
+        let source_line = src.lines().nth(line - 1).unwrap_or_default().to_string();
         // Check if rule is ignored in file config
         let is_ignored_file_config = file_config.get_ignored_rules(file).contains(&kind);
-        let is_ignored = is_ignored_inline || is_ignored_file_config;
-        Self { kind, file: file.display().to_string(), text, line, is_disabled, is_ignored }
+        let severity = file_config.severity(&kind);
+        Self {
+            kind,
+            file: file.display().to_string(),
+            text,
+            line,
+            is_disabled: false,
+            is_ignored: is_ignored_file_config,
+            column,
+            end_line,
+            end_column,
+            source_line,
+            severity,
+            is_baselined: false,
+            rule,
+            is_ignored_by_config: is_ignored_file_config,
+            ignore_reason: None,
+        }
+    }
+
+    #[must_use]
+    /// Returns `true` if the invalid item is in a disabled region, ignored or recorded in the
+    /// baseline, in which case it is not reported.
+    pub const fn is_suppressed(&self) -> bool {
+        self.is_disabled || self.is_ignored || self.is_baselined
     }
 
     #[must_use]
-    /// Returns a string describing the invalid item, which is shown to the user so they can triage
-    /// findings.
+    /// Returns `true` if the invalid item is reported and makes the check fail.
+    pub fn is_error(&self) -> bool {
+        !self.is_suppressed() && self.severity == Severity::Error
+    }
+
+    #[must_use]
+    /// Returns `true` if the invalid item is reported as a warning, which does not make the check
+    /// fail.
+    pub fn is_warning(&self) -> bool {
+        !self.is_suppressed() && self.severity == Severity::Warn
+    }
+
+    #[must_use]
+    /// Returns a snippet of the source code with the invalid item underlined, similar to how
+    /// `rustc` renders diagnostics. Items spanning multiple lines are underlined until the end of
+    /// their first line.
+    pub fn snippet(&self) -> String {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let bar = "|".bold().blue();
+
+        // Tabs are expanded so the underline lines up with the source code.
+        let source_line = self.source_line.replace('\t', "    ");
+        let padding: String = self
+            .source_line
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { "    " } else { " " })
+            .collect();
+        let line_length = self.source_line.chars().count() + 1;
+        let end_column = if self.end_line == self.line { self.end_column } else { line_length };
+        let underline = "^".repeat(end_column.saturating_sub(self.column).max(1));
+
+        format!(
+            "{gutter}{} {}:{}:{}\n{gutter} {bar}\n{} {bar} {source_line}\n{gutter} {bar} {padding}{}\n",
+            "-->".bold().blue(),
+            self.file,
+            self.line,
+            self.column,
+            line_number.bold().blue(),
+            underline.bold().red(),
+        )
+    }
+
+    #[must_use]
+    /// Returns a string describing the invalid item, prefixed with the code of its rule, which is
+    /// shown to the user so they can triage findings.
     pub fn description(&self) -> String {
-        match self.kind {
-            ValidatorKind::Test => {
-                format!("Invalid test name in {} on line {}: {}", self.file, self.line, self.text)
-            }
-            ValidatorKind::Constant => {
-                format!(
-                    "Invalid constant or immutable name in {} on line {}: {}",
-                    self.file, self.line, self.text
-                )
-            }
-            ValidatorKind::Script => {
-                format!("Invalid script interface in {}: {}", self.file, self.text)
-            }
-            ValidatorKind::Src => {
-                format!(
-                    "Invalid src method name in {} on line {}: {}",
-                    self.file, self.line, self.text
-                )
-            }
-            ValidatorKind::Directive => {
-                format!("Invalid directive in {}: {}", self.file, self.text)
-            }
-            ValidatorKind::Variable => {
-                format!(
-                    "Invalid variable name in {} on line {}: {}",
-                    self.file, self.line, self.text
-                )
-            }
-            ValidatorKind::Error => {
-                format!("Invalid error name in {} on line {}: {}", self.file, self.line, self.text)
-            }
-            ValidatorKind::Eip712 => {
-                format!("Invalid EIP712 typehash in {}: {}", self.file, self.text)
-            }
-            ValidatorKind::Import => {
-                format!("Unused import in {} on line {}: {}", self.file, self.line, self.text)
-            }
-        }
+        let description = validators::for_kind(self.kind).describe(self);
+        format!("[{}] {description}", self.rule.code())
     }
 }
 
@@ -113,6 +261,7 @@ impl InvalidItem {
 ///
 /// Two additional file kinds are not included here: `ScriptHelpers` and `TestHelpers`. These are
 /// not currently used in any checks so they are excluded for now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileKind {
     /// Executable script files live in the `scripts` directory and end with `.s.sol`.
     Script,
@@ -195,21 +344,35 @@ impl VisibilitySummary for FunctionDefinition {
 }
 
 #[must_use]
-/// Converts the start offset of a `Loc` to `(line, col)`. Modified from <https://github.com/foundry-rs/foundry/blob/45b9dccdc8584fb5fbf55eb190a880d4e3b0753f/fmt/src/helpers.rs#L54-L70>
+/// Converts the start offset of a `Loc` to a 1-based line number.
 pub fn offset_to_line(content: &str, start: usize) -> usize {
-    debug_assert!(content.len() > start);
+    offset_to_line_column(content, start).0
+}
 
-    let mut line_counter = 1; // First line is `1`.
-    for (offset, c) in content.chars().enumerate() {
-        if c == '\n' {
-            line_counter += 1;
-        }
-        if offset > start {
-            return line_counter;
-        }
+#[must_use]
+/// Converts a byte offset into `content` to a 1-based `(line, column)` pair, where the column is
+/// counted in characters. Offsets past the end of `content` are clamped to its length.
+pub fn offset_to_line_column(content: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(content.len());
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
     }
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[must_use]
+/// Returns the 64-bit FNV-1a hash of the bytes, which is stable across platforms and releases.
+pub fn fnv1a_hash(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
 
-    unreachable!("content.len() > start")
+    bytes
+        .into_iter()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
 }
 
 // ===========================
@@ -220,6 +383,7 @@ pub fn offset_to_line(content: &str, start: usize) -> usize {
 use crate::check::{
     comments::Comments,
     inline_config::{InlineConfig, InvalidInlineConfigItem},
+    validators::Validator,
 };
 use itertools::Itertools;
 use std::path::PathBuf;
@@ -260,13 +424,14 @@ impl ExpectedFindings {
     }
 
     /// Asserts that the number of invalid items found by the validator is equal to the expected
-    /// number for the given content, for each file kind.
+    /// number for the given content, for each file kind. The validator only runs on the file kinds
+    /// it applies to.
     ///
     /// # Panics
     ///
     /// In practice this should not panic unless one of validations fails.
     #[allow(clippy::too_many_lines)]
-    pub fn assert_eq(&self, src: &str, validate: &dyn Fn(&Parsed) -> Vec<InvalidItem>) {
+    pub fn assert_eq(&self, src: &str, validator: &dyn Validator) {
         /// Generates a `Parsed` struct from the given data.
         fn to_parsed(
             path_name: &str,
@@ -296,7 +461,7 @@ impl ExpectedFindings {
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
         let inline_config = InlineConfig::new(inline_config_items, src);
-        let invalid_items_script_helper = validate(&to_parsed(
+        let invalid_items_script_helper = validator.check(&to_parsed(
             "./script/MyContract.sol",
             src,
             pt.clone(),
@@ -308,7 +473,7 @@ impl ExpectedFindings {
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
         let inline_config = InlineConfig::new(inline_config_items, src);
-        let invalid_items_script = validate(&to_parsed(
+        let invalid_items_script = validator.check(&to_parsed(
             "./script/MyContract.s.sol",
             src,
             pt.clone(),
@@ -320,7 +485,7 @@ impl ExpectedFindings {
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
         let inline_config = InlineConfig::new(inline_config_items, src);
-        let invalid_items_src = validate(&to_parsed(
+        let invalid_items_src = validator.check(&to_parsed(
             "./src/MyContract.sol",
             src,
             pt.clone(),
@@ -332,7 +497,7 @@ impl ExpectedFindings {
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
         let inline_config = InlineConfig::new(inline_config_items, src);
-        let invalid_items_test_helper = validate(&to_parsed(
+        let invalid_items_test_helper = validator.check(&to_parsed(
             "./test/MyContract.sol",
             src,
             pt.clone(),
@@ -344,7 +509,7 @@ impl ExpectedFindings {
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
         let inline_config = InlineConfig::new(inline_config_items, src);
-        let invalid_items_test = validate(&to_parsed(
+        let invalid_items_test = validator.check(&to_parsed(
             "./test/MyContract.t.sol",
             src,
             pt.clone(),
@@ -356,7 +521,7 @@ impl ExpectedFindings {
         let (inline_config_items, invalid_inline_config_items): (Vec<_>, Vec<_>) =
             comments.parse_inline_config_items().partition_result();
         let inline_config = InlineConfig::new(inline_config_items, src);
-        let invalid_items_handler = validate(&to_parsed(
+        let invalid_items_handler = validator.check(&to_parsed(
             "./test/MyContract.handler.sol",
             src,
             pt,
@@ -1,26 +1,60 @@
 use crate::check::{
-    utils::{InvalidItem, ValidatorKind},
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, ValidatorKind},
+    validators::Validator,
     Parsed,
 };
 use regex::Regex;
 use solang_parser::pt::{ContractPart, SourceUnitPart, VariableAttribute, VariableDefinition};
-use std::{path::Path, sync::LazyLock};
+use std::sync::LazyLock;
 
 // A regex matching valid constant names, see the `validate_constant_names_regex` test for examples.
 static RE_VALID_CONSTANT_NAME: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^(?:[$_]*[A-Z0-9][$_]*){1,}$").unwrap());
 
-const fn is_matching_file(_file: &Path) -> bool {
-    true
-}
-
-#[must_use]
 /// Validates that constant and immutable variable names are in `ALL_CAPS`.
-pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
-    if !is_matching_file(&parsed.file) {
-        return Vec::new();
+pub struct ConstantNames;
+
+impl Validator for ConstantNames {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Constant
+    }
+
+    fn id(&self) -> &'static str {
+        "constant"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invalid constant or immutable name"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[CONSTANT_NAME]
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        None
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
     }
+}
+
+/// The `constant-name` rule.
+const CONSTANT_NAME: RuleInfo = RuleInfo {
+    rule: Rule::ConstantName,
+    code: "SL401",
+    name: "constant-name",
+    summary: "Constant and immutable names must be in ALL_CAPS.",
+    rationale: "Writing constants and immutables in ALL_CAPS distinguishes values that can never \
+                change from storage variables, which is the convention of the Solidity style \
+                guide.",
+    bad_example: "uint256 public constant maxSupply = 1000;",
+    good_example: "uint256 public constant MAX_SUPPLY = 1000;",
+};
 
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     let mut invalid_items: Vec<InvalidItem> = Vec::new();
     for element in &parsed.pt.0 {
         match element {
@@ -63,7 +97,7 @@ fn validate_name(parsed: &Parsed, v: &VariableDefinition) -> Option<InvalidItem>
         if is_valid_constant_name(name_string) {
             None
         } else {
-            Some(InvalidItem::new(ValidatorKind::Constant, parsed, name.loc, name_string.clone()))
+            Some(InvalidItem::new(Rule::ConstantName, parsed, name.loc, name_string.clone()))
         }
     })
 }
@@ -92,7 +126,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings::new(2);
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &ConstantNames);
     }
 
     #[test]
@@ -0,0 +1,108 @@
+use crate::check::{
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, ValidatorKind},
+    validators::Validator,
+    Parsed,
+};
+
+/// Validates that inline `scopelint:` directives are valid.
+pub struct Directives;
+
+impl Validator for Directives {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Directive
+    }
+
+    fn id(&self) -> &'static str {
+        "directive"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invalid directive"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[INVALID_DIRECTIVE, MISSING_IGNORE_REASON]
+    }
+
+    fn describe(&self, item: &InvalidItem) -> String {
+        format!("{} in {}: {}", self.description(), item.file, item.text)
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        None
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
+    }
+}
+
+/// The `invalid-directive` rule.
+const INVALID_DIRECTIVE: RuleInfo = RuleInfo {
+    rule: Rule::InvalidDirective,
+    code: "SL901",
+    name: "invalid-directive",
+    summary: "Inline `scopelint:` directives must be valid.",
+    rationale: "A directive that scopelint does not understand has no effect, so code that was \
+                meant to be ignored is still checked, or a typo hides the intent of the comment.",
+    bad_example: "// scopelint: ignore-nxt-line",
+    good_example: "// scopelint: ignore-next-line",
+};
+
+/// The `missing-ignore-reason` rule.
+const MISSING_IGNORE_REASON: RuleInfo = RuleInfo {
+    rule: Rule::MissingIgnoreReason,
+    code: "SL903",
+    name: "missing-ignore-reason",
+    summary: "Inline ignore directives must give a reason after `--`, if `require_reason` is set \
+              in `.scopelint`.",
+    rationale: "Ignoring a rule waives a convention. Recording why next to the directive lets \
+                reviewers and auditors judge whether the exception is still justified.",
+    bad_example: "// scopelint: ignore-error-next-line",
+    good_example: "// scopelint: ignore-error-next-line -- legacy ABI compatibility",
+};
+
+/// Reports directives that could not be parsed and, if required by `.scopelint`, ignore directives
+/// that do not give a reason.
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    let mut invalid_items: Vec<InvalidItem> = parsed
+        .invalid_inline_config_items
+        .iter()
+        .map(|(loc, item)| InvalidItem::new(Rule::InvalidDirective, parsed, *loc, item.to_string()))
+        .collect();
+
+    if parsed.file_config.requires_ignore_reason() {
+        for loc in parsed.inline_config.ignore_directives_without_reason() {
+            let directive = parsed.src[loc.start()..loc.end()].trim();
+            invalid_items.push(InvalidItem::new(
+                Rule::MissingIgnoreReason,
+                parsed,
+                *loc,
+                format!("Missing reason for ignore directive: {directive}"),
+            ));
+        }
+    }
+    invalid_items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::utils::ExpectedFindings;
+
+    #[test]
+    fn test_validate() {
+        let content = r"
+            contract MyContract {
+                // scopelint: ignore-nxt-line
+                uint256 public x;
+                // scopelint: ignore-next-line -- reason
+                uint256 public y;
+            }
+        ";
+
+        let expected_findings = ExpectedFindings::new(1);
+        expected_findings.assert_eq(content, &Directives);
+    }
+}
@@ -2,16 +2,77 @@ use regex::Regex;
 use solang_parser::pt::{ContractPart, SourceUnitPart, VariableDefinition};
 
 use crate::check::{
-    utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, ValidatorKind},
+    validators::Validator,
     Parsed,
 };
-#[must_use]
-// Validates that EIP712 typehash parameter counts match their usage in abi.encode calls.
-pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
-    if !is_matching_file(parsed) {
-        return Vec::new();
+
+/// Validates that EIP712 typehashes match their corresponding struct definitions.
+pub struct Eip712Typehash;
+
+impl Validator for Eip712Typehash {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Eip712
+    }
+
+    fn id(&self) -> &'static str {
+        "eip712"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invalid EIP712 typehash"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[EIP712_PARAMETER_MISMATCH, EIP712_MISSING_TYPE_STRING]
     }
 
+    fn describe(&self, item: &InvalidItem) -> String {
+        format!("{} in {}: {}", self.description(), item.file, item.text)
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        Some(&[FileKind::Src])
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
+    }
+}
+
+/// The `eip712-parameter-mismatch` rule.
+const EIP712_PARAMETER_MISMATCH: RuleInfo = RuleInfo {
+    rule: Rule::Eip712ParameterMismatch,
+    code: "SL701",
+    name: "eip712-parameter-mismatch",
+    summary: "EIP-712 typehashes must have as many parameters as the data encoded with them.",
+    rationale: "When the type string and the encoded data disagree, the struct hash differs from \
+                the one computed by wallets and off-chain code, so every signature is rejected.",
+    bad_example: "bytes32 public constant PERMIT_TYPEHASH =\n  \
+                  keccak256(\"Permit(address owner,uint256 value)\");\n\n\
+                  abi.encode(PERMIT_TYPEHASH, _owner, _value, _nonce);",
+    good_example: "bytes32 public constant PERMIT_TYPEHASH =\n  \
+                   keccak256(\"Permit(address owner,uint256 value,uint256 nonce)\");\n\n\
+                   abi.encode(PERMIT_TYPEHASH, _owner, _value, _nonce);",
+};
+
+/// The `eip712-missing-type-string` rule.
+const EIP712_MISSING_TYPE_STRING: RuleInfo = RuleInfo {
+    rule: Rule::Eip712MissingTypeString,
+    code: "SL702",
+    name: "eip712-missing-type-string",
+    summary: "EIP-712 typehashes must be the keccak256 hash of a type string.",
+    rationale: "A typehash that is not computed from its type string is hard to verify and easily \
+                gets out of sync with the struct it describes, causing signature mismatches.",
+    bad_example: "bytes32 public constant PERMIT_TYPEHASH =\n  \
+                  0x6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9;",
+    good_example: "bytes32 public constant PERMIT_TYPEHASH =\n  \
+                   keccak256(\"Permit(address owner,uint256 value,uint256 nonce)\");",
+};
+
+// Validates that EIP712 typehash parameter counts match their usage in abi.encode calls.
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     let mut invalid_items: Vec<InvalidItem> = Vec::new();
     let mut typehash_variables: Vec<(String, String, solang_parser::pt::Loc, Option<String>)> =
         Vec::new();
@@ -43,7 +104,7 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
             for usage_param_count in usages {
                 if usage_param_count != param_count {
                     invalid_items.push(InvalidItem::new(
-                        ValidatorKind::Eip712,
+                        Rule::Eip712ParameterMismatch,
                         parsed,
                         loc,
                         format!("EIP712 typehash '{typehash_name}' parameter mismatch: typehash defines {param_count} parameters but abi.encode usage uses {usage_param_count} parameters"),
@@ -53,7 +114,7 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
         } else {
             // No keccak256 string found - this is definitely an issue
             invalid_items.push(InvalidItem::new(
-                ValidatorKind::Eip712,
+                Rule::Eip712MissingTypeString,
                 parsed,
                 loc,
                 format!("Typehash '{typehash_name}' for struct '{expected_struct_name}' has no keccak256 string - this will cause signature mismatches"),
@@ -64,10 +125,6 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     invalid_items
 }
 
-fn is_matching_file(parsed: &Parsed) -> bool {
-    parsed.file.is_file_kind(FileKind::Src, &parsed.path_config)
-}
-
 fn extract_typehash_variable(
     v: &VariableDefinition,
 ) -> Option<(String, String, solang_parser::pt::Loc, Option<String>)> {
@@ -177,7 +234,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, test: 0, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &Eip712Typehash);
     }
 
     #[test]
@@ -194,7 +251,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 0, test: 0, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &Eip712Typehash);
     }
 
     #[test]
@@ -211,7 +268,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, test: 0, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &Eip712Typehash);
     }
 
     #[test]
@@ -233,7 +290,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, test: 0, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &Eip712Typehash);
     }
 
     #[test]
@@ -251,7 +308,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 0, test: 0, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &Eip712Typehash);
     }
 
     #[test]
@@ -269,7 +326,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, test: 0, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &Eip712Typehash);
     }
 
     #[test]
@@ -286,7 +343,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, test: 0, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &Eip712Typehash);
     }
 
     #[test]
@@ -311,7 +368,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, test: 0, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &Eip712Typehash);
     }
 
     #[test]
@@ -336,6 +393,6 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, test: 0, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &Eip712Typehash);
     }
 }
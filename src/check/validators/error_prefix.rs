@@ -1,16 +1,55 @@
 use solang_parser::pt::{ContractPart, ErrorDefinition, SourceUnitPart};
 
 use crate::check::{
-    utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, ValidatorKind},
+    validators::Validator,
     Parsed,
 };
-#[must_use]
-/// Validates that error names are prefixed with `ContractName_`
-pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
-    if !is_matching_file(parsed) {
-        return Vec::new();
+
+/// Validates that error names are prefixed with `ContractName_`.
+pub struct ErrorPrefix;
+
+impl Validator for ErrorPrefix {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Error
     }
 
+    fn id(&self) -> &'static str {
+        "error"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invalid error name"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[ERROR_PREFIX]
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        Some(&[FileKind::Src, FileKind::Test, FileKind::Handler])
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
+    }
+}
+
+/// The `error-prefix` rule.
+const ERROR_PREFIX: RuleInfo = RuleInfo {
+    rule: Rule::ErrorPrefix,
+    code: "SL601",
+    name: "error-prefix",
+    summary: "Custom errors must be prefixed with the name of the contract that defines them.",
+    rationale: "Prefixing errors with the contract name makes it clear which contract reverted \
+                when decoding revert data, and avoids selector collisions between contracts that \
+                define errors with the same name.",
+    bad_example: "contract Vault {\n  error Unauthorized();\n}",
+    good_example: "contract Vault {\n  error Vault_Unauthorized();\n}",
+};
+
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     let mut invalid_items: Vec<InvalidItem> = Vec::new();
 
     for element in &parsed.pt.0 {
@@ -33,13 +72,6 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     invalid_items
 }
 
-fn is_matching_file(parsed: &Parsed) -> bool {
-    let file = &parsed.file;
-    file.is_file_kind(FileKind::Src, &parsed.path_config) ||
-        file.is_file_kind(FileKind::Test, &parsed.path_config) ||
-        file.is_file_kind(FileKind::Handler, &parsed.path_config)
-}
-
 fn validate_name(
     parsed: &Parsed,
     e: &ErrorDefinition,
@@ -58,7 +90,7 @@ fn validate_name(
         None // Valid - error name is prefixed with contract name
     } else {
         Some(InvalidItem::new(
-            ValidatorKind::Error,
+            Rule::ErrorPrefix,
             parsed,
             error_loc,
             format!("Error '{error_name}' should be prefixed with '{contract_name}_'"),
@@ -87,7 +119,7 @@ mod tests {
 
         let expected_findings =
             ExpectedFindings { src: 2, test: 2, handler: 2, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &ErrorPrefix);
     }
 
     #[test]
@@ -103,7 +135,7 @@ mod tests {
         // Only one error should be found (the one without ignore directive)
         let expected_findings =
             ExpectedFindings { src: 1, test: 1, handler: 1, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &ErrorPrefix);
     }
 
     #[test]
@@ -121,7 +153,7 @@ mod tests {
         // Only one error should be found (outside the ignore region)
         let expected_findings =
             ExpectedFindings { src: 1, test: 1, handler: 1, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &ErrorPrefix);
     }
 
     #[test]
@@ -138,6 +170,6 @@ contract MyContract {
 
         // All errors should be ignored for the entire file
         let expected_findings = ExpectedFindings::new(0);
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &ErrorPrefix);
     }
 }
@@ -1,29 +1,98 @@
-use colored::Colorize;
-use std::{error::Error, fs, process};
+use crate::error::{Error, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
 
-/// Validates that Solidity and TOML files are formatted correctly.
+/// The name that selects the formatting check in `--only` and `--skip`.
+pub const RULE: &str = "fmt";
+
+/// The result of checking the formatting of a project with [`check`].
+#[derive(Debug, Default)]
+pub struct FormatCheck {
+    /// Whether `forge fmt --check` passed without printing warnings or errors.
+    pub forge_ok: bool,
+    /// The warnings and errors printed by `forge fmt --check`.
+    pub forge_stderr: String,
+    /// The files that are not formatted, relative to the project root.
+    pub unformatted_files: Vec<String>,
+}
+
+impl FormatCheck {
+    /// Returns `true` if all files are formatted.
+    #[must_use]
+    pub const fn is_valid(&self) -> bool {
+        self.forge_ok && self.unformatted_files.is_empty()
+    }
+}
+
+/// Checks that Solidity and TOML files of the project at `root` are formatted correctly, without
+/// printing anything.
+///
+/// If `files` is given, only those Solidity files are checked, with paths
+/// relative to `root`, and `foundry.toml` is not.
 /// # Errors
-/// Returns an error if formatting is invalid or parsing fails.
-pub fn validate(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
-    // Check Solidity with `forge fmt`.
-    let forge_status = process::Command::new("forge").arg("fmt").arg("--check").output()?;
+/// Returns an error if `forge` cannot be run or `foundry.toml` cannot be read.
+pub fn check(
+    taplo_opts: taplo::formatter::Options,
+    root: &Path,
+    files: Option<&[PathBuf]>,
+) -> Result<FormatCheck> {
+    let mut result = FormatCheck { forge_ok: true, ..FormatCheck::default() };
 
-    // Print any warnings/errors from `forge fmt`.
-    let stderr = String::from_utf8(forge_status.stderr)?;
-    let forge_ok = forge_status.status.success() && stderr.is_empty();
-    print!("{stderr}"); // Prints nothing if stderr is empty.
+    // Check Solidity with `forge fmt`, which checks the whole project if no paths are passed.
+    if files.is_none_or(|files| !files.is_empty()) {
+        let forge_status = process::Command::new("forge")
+            .arg("fmt")
+            .arg("--check")
+            .args(files.unwrap_or_default())
+            .current_dir(root)
+            .output()
+            .map_err(|err| Error::ExternalTool(format!("Failed to run forge: {err}")))?;
+        result.forge_stderr = String::from_utf8_lossy(&forge_status.stderr).into_owned();
+        result.forge_ok = forge_status.status.success() && result.forge_stderr.is_empty();
+        result.unformatted_files =
+            forge_diff_files(&String::from_utf8_lossy(&forge_status.stdout), root);
+    }
 
     // Check TOML with `taplo fmt`
-    let config_orig = fs::read_to_string("./foundry.toml")?;
-    let config_fmt = taplo::formatter::format(&config_orig, taplo_opts);
-    let taplo_ok = config_orig == config_fmt;
-
-    if !forge_ok || !taplo_ok {
-        eprintln!(
-            "{}: Formatting validation failed, run `scopelint fmt` to fix",
-            "error".bold().red()
+    if files.is_none() {
+        let config_path = root.join("foundry.toml");
+        let config_orig = fs::read_to_string(&config_path)
+            .map_err(|err| Error::io(format!("Failed to read {}", config_path.display()), &err))?;
+        if config_orig != taplo::formatter::format(&config_orig, taplo_opts) {
+            result.unformatted_files.push("foundry.toml".to_string());
+        }
+    }
+
+    Ok(result)
+}
+
+/// Returns the files listed in the `Diff in <file>:` headers of `forge fmt --check` output,
+/// relative to the project `root`.
+fn forge_diff_files(output: &str, root: &Path) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("Diff in ")?.trim_end().strip_suffix(':'))
+        .map(|file| {
+            let path = Path::new(file);
+            path.strip_prefix(root).unwrap_or(path).display().to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forge_diff_files() {
+        let root = Path::new("/path/to/project");
+        let output = format!(
+            "Diff in {}/src/Counter.sol:\n1    |-contract A{{}}\n1    |+contract A {{}}\nDiff in test/Counter.t.sol:\n",
+            root.display()
         );
-        return Err("Invalid fmt found".into());
+        assert_eq!(forge_diff_files(&output, root), vec!["src/Counter.sol", "test/Counter.t.sol"]);
     }
-    Ok(())
 }
@@ -1,6 +1,19 @@
+use crate::check::{
+    rules::RuleInfo,
+    utils::{FileKind, InvalidItem, IsFileKind, Severity, ValidatorKind},
+    Parsed,
+};
+
+/// Describes the files that could not be parsed.
+pub mod parse_errors;
+
 /// Validates that Solidity and TOML files are formatted correctly.
+#[cfg(feature = "fmt")]
 pub mod formatting;
 
+/// Validates that inline `scopelint:` directives are valid.
+pub mod directives;
+
 /// Validates that constant and immutable variable names are in `ALL_CAPS`.
 pub mod constant_names;
 
@@ -27,3 +40,144 @@ pub mod eip712_typehash;
 
 /// Validates that all imported symbols are actually used in the file.
 pub mod unused_imports;
+
+/// Validates that inline ignore directives suppress at least one finding.
+pub mod unused_directives;
+
+/// A check that runs on each parsed Solidity file and reports its findings under a rule.
+///
+/// Validators are registered in [`VALIDATORS`], and the names, codes, descriptions and default
+/// severities of their rules are derived from them. See `DEV.md` for how to add a new rule.
+pub trait Validator: Sync {
+    /// The kind of findings reported by the validator.
+    fn kind(&self) -> ValidatorKind;
+
+    /// The rule name used in `.scopelint` files and inline directives. Validators reporting under
+    /// the same kind must use the same name.
+    fn id(&self) -> &'static str;
+
+    /// A short description of what a finding is, e.g. `Invalid test name`.
+    fn description(&self) -> &'static str;
+
+    /// The rules reported by the validator, with their codes and explanations.
+    fn rules(&self) -> &'static [RuleInfo];
+
+    /// The severity of the findings if `.scopelint` does not configure one.
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Returns the message printed for an invalid item reported by the validator, without its
+    /// rule code.
+    fn describe(&self, item: &InvalidItem) -> String {
+        format!("{} in {} on line {}: {}", self.description(), item.file, item.line, item.text)
+    }
+
+    /// The kinds of files the validator applies to, or `None` if it applies to every Solidity
+    /// file.
+    fn file_kinds(&self) -> Option<&'static [FileKind]>;
+
+    /// Returns the invalid items in the file. Only called for files the validator applies to.
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem>;
+
+    /// Returns the source of the file with the given invalid items of this validator fixed, or
+    /// `None` if the validator cannot fix them or there was nothing to change.
+    fn fix(&self, _parsed: &Parsed, _items: &[&InvalidItem]) -> Option<String> {
+        None
+    }
+
+    /// Returns `true` if the validator applies to the file.
+    fn is_matching_file(&self, parsed: &Parsed) -> bool {
+        self.file_kinds().is_none_or(|kinds| {
+            kinds.iter().any(|kind| parsed.file.is_file_kind(*kind, &parsed.path_config))
+        })
+    }
+
+    /// Validates the file if the validator applies to it.
+    fn check(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        if self.is_matching_file(parsed) {
+            self.validate(parsed)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// All validators, in the order they run.
+///
+/// Unused directives must come last, since they are only known once every other validator has run
+/// on the file. Fixes are applied in reverse order, so unused directives are removed while their
+/// positions still match the source.
+pub static VALIDATORS: [&dyn Validator; 12] = [
+    &parse_errors::ParseErrors,
+    &directives::Directives,
+    &test_names::TestNames,
+    &src_names_internal::SrcNamesInternal,
+    &script_has_public_run_method::ScriptHasPublicRunMethod,
+    &constant_names::ConstantNames,
+    &src_spdx_header::SrcSpdxHeader,
+    &variable_names::VariableNames,
+    &error_prefix::ErrorPrefix,
+    &eip712_typehash::Eip712Typehash,
+    &unused_imports::UnusedImports,
+    &unused_directives::UnusedDirectives,
+];
+
+/// Returns the first registered validator reporting findings of the given kind.
+///
+/// # Panics
+///
+/// Panics if no validator reports findings of the kind.
+#[must_use]
+pub fn for_kind(kind: ValidatorKind) -> &'static dyn Validator {
+    VALIDATORS
+        .iter()
+        .copied()
+        .find(|validator| validator.kind() == kind)
+        .expect("every kind is reported by a validator")
+}
+
+/// Maps a rule name (e.g., "error") to a `ValidatorKind`, if a validator reports under it.
+#[must_use]
+pub fn kind_from_id(id: &str) -> Option<ValidatorKind> {
+    VALIDATORS.iter().find(|validator| validator.id() == id).map(|validator| validator.kind())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn test_every_kind_has_a_validator() {
+        for kind in ValidatorKind::all() {
+            assert_eq!(kind_from_id(kind.name()), Some(kind), "{}", kind.name());
+        }
+        assert_eq!(kind_from_id("unknown"), None);
+    }
+
+    #[test]
+    fn test_validators_of_a_kind_agree() {
+        for validator in VALIDATORS {
+            let first = for_kind(validator.kind());
+            assert_eq!(validator.id(), first.id());
+            assert_eq!(validator.description(), first.description(), "{}", validator.id());
+            assert_eq!(
+                validator.default_severity(),
+                first.default_severity(),
+                "{}",
+                validator.id()
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_rule_has_one_validator() {
+        let rules: Vec<_> = VALIDATORS
+            .iter()
+            .flat_map(|validator| validator.rules())
+            .map(|info| info.rule)
+            .collect();
+        assert_eq!(rules.iter().unique().count(), rules.len());
+    }
+}
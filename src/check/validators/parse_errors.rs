@@ -0,0 +1,55 @@
+use crate::check::{
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, ValidatorKind},
+    validators::Validator,
+    Parsed,
+};
+
+/// Describes the files that could not be parsed.
+///
+/// Parse errors are found before any validator runs, see [`crate::check::ParseError`], so this
+/// validator never reports findings itself.
+pub struct ParseErrors;
+
+impl Validator for ParseErrors {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Parse
+    }
+
+    fn id(&self) -> &'static str {
+        "parse"
+    }
+
+    fn description(&self) -> &'static str {
+        "Failed to parse"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[PARSE_ERROR]
+    }
+
+    fn describe(&self, item: &InvalidItem) -> String {
+        format!("{} {} on line {}: {}", self.description(), item.file, item.line, item.text)
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        None
+    }
+
+    fn validate(&self, _parsed: &Parsed) -> Vec<InvalidItem> {
+        Vec::new()
+    }
+}
+
+/// The `parse-error` rule.
+const PARSE_ERROR: RuleInfo = RuleInfo {
+    rule: Rule::ParseError,
+    code: "SL001",
+    name: "parse-error",
+    summary: "Solidity files must be parsable.",
+    rationale: "A file that cannot be parsed is not checked by any other rule, so its findings \
+                would go unnoticed. The file either has a syntax error or uses syntax that the \
+                parser does not support yet.",
+    bad_example: "contract Counter {\n  function increment() public {\n}",
+    good_example: "contract Counter {\n  function increment() public {}\n}",
+};
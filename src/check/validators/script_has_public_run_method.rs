@@ -1,23 +1,60 @@
 use crate::check::{
-    utils::{FileKind, InvalidItem, IsFileKind, Name, ValidatorKind, VisibilitySummary},
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, Name, ValidatorKind, VisibilitySummary},
+    validators::Validator,
     Parsed,
 };
 use solang_parser::pt::{ContractPart, ContractTy, Loc, SourceUnitPart};
-fn is_matching_file(parsed: &Parsed) -> bool {
-    parsed.file.is_file_kind(FileKind::Script, &parsed.path_config)
-}
 
-#[must_use]
 /// Validates that a script has a single public method named `run`.
-///
+pub struct ScriptHasPublicRunMethod;
+
+impl Validator for ScriptHasPublicRunMethod {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Script
+    }
+
+    fn id(&self) -> &'static str {
+        "script"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invalid script interface"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[SCRIPT_RUN_METHOD]
+    }
+
+    fn describe(&self, item: &InvalidItem) -> String {
+        format!("{} in {}: {}", self.description(), item.file, item.text)
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        Some(&[FileKind::Script])
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
+    }
+}
+
+/// The `script-run-method` rule.
+const SCRIPT_RUN_METHOD: RuleInfo = RuleInfo {
+    rule: Rule::ScriptRunMethod,
+    code: "SL301",
+    name: "script-run-method",
+    summary: "Scripts must have a public `run` method.",
+    rationale: "`forge script` calls the `run` method by default, so every script must have one \
+                to be executable without specifying a signature.",
+    bad_example: "contract Deploy is Script {\n  function deploy() public {}\n}",
+    good_example: "contract Deploy is Script {\n  function run() public {}\n}",
+};
+
 /// # Panics
 ///
 /// Panics if the script has no contract definition.
-pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
-    if !is_matching_file(parsed) {
-        return Vec::new();
-    }
-
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     // The location of findings spans multiple lines, so we use the contract location.
     let mut contract_loc: Option<Loc> = None;
 
@@ -57,7 +94,7 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     match public_methods.len() {
         0 => {
             vec![InvalidItem::new(
-                ValidatorKind::Script,
+                Rule::ScriptRunMethod,
                 parsed,
                 loc,
                 "No `run` method found".to_string(),
@@ -68,7 +105,7 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
                 Vec::new()
             } else {
                 vec![InvalidItem::new(
-                    ValidatorKind::Script,
+                    Rule::ScriptRunMethod,
                     parsed,
                     loc,
                     "No `run` method found".to_string(),
@@ -131,15 +168,15 @@ mod tests {
         ";
 
         let expected_findings_good = ExpectedFindings::new(0);
-        expected_findings_good.assert_eq(content_good, &validate);
-        expected_findings_good.assert_eq(content_good_variant0, &validate);
-        expected_findings_good.assert_eq(content_good_variant1, &validate);
-        expected_findings_good.assert_eq(content_good_variant2, &validate);
+        expected_findings_good.assert_eq(content_good, &ScriptHasPublicRunMethod);
+        expected_findings_good.assert_eq(content_good_variant0, &ScriptHasPublicRunMethod);
+        expected_findings_good.assert_eq(content_good_variant1, &ScriptHasPublicRunMethod);
+        expected_findings_good.assert_eq(content_good_variant2, &ScriptHasPublicRunMethod);
 
         let expected_findings_bad = ExpectedFindings { script: 1, ..Default::default() };
-        expected_findings_bad.assert_eq(content_bad0, &validate);
-        expected_findings_bad.assert_eq(content_bad1, &validate);
-        expected_findings_bad.assert_eq(content_bad2_variant0, &validate);
+        expected_findings_bad.assert_eq(content_bad0, &ScriptHasPublicRunMethod);
+        expected_findings_bad.assert_eq(content_bad1, &ScriptHasPublicRunMethod);
+        expected_findings_bad.assert_eq(content_bad2_variant0, &ScriptHasPublicRunMethod);
     }
 
     #[test]
@@ -156,6 +193,6 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings::new(0);
-        expected_findings.assert_eq(content_with_interface, &validate);
+        expected_findings.assert_eq(content_with_interface, &ScriptHasPublicRunMethod);
     }
 }
@@ -1,20 +1,56 @@
 use crate::check::{
-    utils::{FileKind, InvalidItem, IsFileKind, Name, ValidatorKind, VisibilitySummary},
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, Name, ValidatorKind, VisibilitySummary},
+    validators::Validator,
     Parsed,
 };
 use solang_parser::pt::{ContractPart, ContractTy, FunctionDefinition, SourceUnitPart};
 
-fn is_matching_file(parsed: &Parsed) -> bool {
-    parsed.file.is_file_kind(FileKind::Src, &parsed.path_config)
-}
-
-#[must_use]
 /// Validates that internal and private function names are prefixed with an underscore.
-pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
-    if !is_matching_file(parsed) {
-        return Vec::new();
+pub struct SrcNamesInternal;
+
+impl Validator for SrcNamesInternal {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Src
+    }
+
+    fn id(&self) -> &'static str {
+        "src"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invalid src method name"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[INTERNAL_FUNCTION_NAME]
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        Some(&[FileKind::Src])
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
     }
+}
+
+/// The `internal-function-name` rule.
+const INTERNAL_FUNCTION_NAME: RuleInfo = RuleInfo {
+    rule: Rule::InternalFunctionName,
+    code: "SL101",
+    name: "internal-function-name",
+    summary: "Internal and private functions in source contracts must be prefixed with an \
+              underscore.",
+    rationale:
+        "An underscore prefix makes it obvious at the call site that a function is not part \
+                of the contract's external interface, so reviewers can tell which code paths can \
+                be reached directly by users.",
+    bad_example: "function computeFee(uint256 _amount) internal {}",
+    good_example: "function _computeFee(uint256 _amount) internal {}",
+};
 
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     let mut invalid_items: Vec<InvalidItem> = Vec::new();
     for element in &parsed.pt.0 {
         match element {
@@ -23,13 +59,11 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
                     invalid_items.push(invalid_item);
                 }
             }
-            SourceUnitPart::ContractDefinition(c) => {
-                if !matches!(c.ty, ContractTy::Library(_)) {
-                    for el in &c.parts {
-                        if let ContractPart::FunctionDefinition(f) = el {
-                            if let Some(invalid_item) = validate_name(parsed, f) {
-                                invalid_items.push(invalid_item);
-                            }
+            SourceUnitPart::ContractDefinition(c) if !matches!(c.ty, ContractTy::Library(_)) => {
+                for el in &c.parts {
+                    if let ContractPart::FunctionDefinition(f) = el {
+                        if let Some(invalid_item) = validate_name(parsed, f) {
+                            invalid_items.push(invalid_item);
                         }
                     }
                 }
@@ -47,7 +81,7 @@ fn is_valid_internal_or_private_name(name: &str) -> bool {
 fn validate_name(parsed: &Parsed, f: &FunctionDefinition) -> Option<InvalidItem> {
     let name = f.name();
     if f.is_internal_or_private() && !is_valid_internal_or_private_name(&name) {
-        Some(InvalidItem::new(ValidatorKind::Src, parsed, f.name_loc, name))
+        Some(InvalidItem::new(Rule::InternalFunctionName, parsed, f.name_loc, name))
     } else {
         None
     }
@@ -77,6 +111,6 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 2, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &SrcNamesInternal);
     }
 }
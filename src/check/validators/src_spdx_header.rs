@@ -1,19 +1,54 @@
 use crate::check::{
-    utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, ValidatorKind},
+    validators::Validator,
     Parsed,
 };
-/// Check if a file is a source file
-fn is_matching_file(parsed: &Parsed) -> bool {
-    parsed.file.is_file_kind(FileKind::Src, &parsed.path_config)
-}
 
-#[must_use]
 /// Validates that source files have SPDX license headers.
-pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
-    if !is_matching_file(parsed) {
-        return Vec::new();
+pub struct SrcSpdxHeader;
+
+impl Validator for SrcSpdxHeader {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Src
+    }
+
+    fn id(&self) -> &'static str {
+        "src"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invalid src method name"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[SPDX_HEADER]
     }
 
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        Some(&[FileKind::Src])
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
+    }
+}
+
+/// The `spdx-header` rule.
+const SPDX_HEADER: RuleInfo = RuleInfo {
+    rule: Rule::SpdxHeader,
+    code: "SL102",
+    name: "spdx-header",
+    summary: "Source files must start with an SPDX license identifier.",
+    rationale:
+        "The Solidity compiler warns about files without a license identifier, and verified \
+                source code on block explorers should state the license it is published under.",
+    bad_example: "pragma solidity ^0.8.17;\n\ncontract Counter {}",
+    good_example:
+        "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Counter {}",
+};
+
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     let mut invalid_items: Vec<InvalidItem> = Vec::new();
 
     // Check if SPDX header is present
@@ -21,7 +56,7 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
         // Create a simple location for file-level issues
         let loc = solang_parser::pt::Loc::File(0, 0, 0);
         invalid_items.push(InvalidItem::new(
-            ValidatorKind::Src,
+            Rule::SpdxHeader,
             parsed,
             loc,
             "Missing SPDX-License-Identifier header".to_string(),
@@ -81,7 +116,7 @@ mod tests {
             }
         ";
 
-        ExpectedFindings::new(0).assert_eq(content, &validate);
+        ExpectedFindings::new(0).assert_eq(content, &SrcSpdxHeader);
     }
 
     #[test]
@@ -95,7 +130,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &SrcSpdxHeader);
     }
 
     #[test]
@@ -110,7 +145,7 @@ mod tests {
             }
         ";
 
-        ExpectedFindings::new(0).assert_eq(content, &validate);
+        ExpectedFindings::new(0).assert_eq(content, &SrcSpdxHeader);
     }
 
     #[test]
@@ -125,7 +160,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &SrcSpdxHeader);
     }
 
     #[test]
@@ -140,6 +175,6 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { src: 1, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &SrcSpdxHeader);
     }
 }
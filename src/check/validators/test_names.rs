@@ -1,5 +1,7 @@
 use crate::check::{
-    utils::{FileKind, InvalidItem, IsFileKind, Name, ValidatorKind, VisibilitySummary},
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, Name, ValidatorKind, VisibilitySummary},
+    validators::Validator,
     Parsed,
 };
 use regex::Regex;
@@ -11,17 +13,51 @@ static RE_VALID_TEST_NAME: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^test(Fork)?(Fuzz)?(_Revert(If|When|On|Given))?_(\w+)*$").unwrap()
 });
 
-fn is_matching_file(parsed: &Parsed) -> bool {
-    parsed.file.is_file_kind(FileKind::Test, &parsed.path_config)
-}
-
-#[must_use]
 /// Validates that test names are in the correct format.
-pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
-    if !is_matching_file(parsed) {
-        return Vec::new();
+pub struct TestNames;
+
+impl Validator for TestNames {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Test
+    }
+
+    fn id(&self) -> &'static str {
+        "test"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invalid test name"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[TEST_NAME]
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        Some(&[FileKind::Test])
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
     }
+}
+
+/// The `test-name` rule.
+const TEST_NAME: RuleInfo = RuleInfo {
+    rule: Rule::TestName,
+    code: "SL201",
+    name: "test-name",
+    summary: "Test names must follow the \
+              `test(Fork)?(Fuzz)?(_Revert(If|When|On|Given))?_Description` convention.",
+    rationale: "Consistent test names document the behavior under test, make it easy to run a \
+                subset of tests with `--match-test`, and are used by `scopelint spec` to generate \
+                a specification. Tests that expect a revert state the condition in the name.",
+    bad_example: "function testIncrementWorks() public {}",
+    good_example: "function test_IncrementsTheNumber() public {}\n\
+                   function testFuzz_RevertIf_CallerIsNotOwner(address _caller) public {}",
+};
 
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     let mut invalid_items: Vec<InvalidItem> = Vec::new();
     for element in &parsed.pt.0 {
         match element {
@@ -82,7 +118,7 @@ fn is_test_function(f: &FunctionDefinition) -> bool {
 fn validate_name(parsed: &Parsed, f: &FunctionDefinition) -> Option<InvalidItem> {
     let name = f.name();
     if is_test_function(f) && !is_valid_test_name(&name) {
-        Some(InvalidItem::new(ValidatorKind::Test, parsed, f.name_loc, name))
+        Some(InvalidItem::new(Rule::TestName, parsed, f.name_loc, name))
     } else {
         None
     }
@@ -120,7 +156,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings { test: 3, ..ExpectedFindings::default() };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &TestNames);
     }
 
     #[test]
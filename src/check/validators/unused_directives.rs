@@ -0,0 +1,211 @@
+use crate::check::{
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, ValidatorKind},
+    validators::Validator,
+    Parsed,
+};
+
+/// Validates that inline ignore directives suppress at least one finding.
+pub struct UnusedDirectives;
+
+impl Validator for UnusedDirectives {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::UnusedDirective
+    }
+
+    fn id(&self) -> &'static str {
+        "unused-directive"
+    }
+
+    fn description(&self) -> &'static str {
+        "Unused directive"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[UNUSED_DIRECTIVE]
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        None
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
+    }
+
+    fn fix(&self, parsed: &Parsed, items: &[&InvalidItem]) -> Option<String> {
+        fix_source(&parsed.src, items)
+    }
+}
+
+/// The `unused-directive` rule.
+const UNUSED_DIRECTIVE: RuleInfo = RuleInfo {
+    rule: Rule::UnusedDirective,
+    code: "SL902",
+    name: "unused-directive",
+    summary: "Inline ignore directives must suppress at least one finding.",
+    rationale: "An ignore directive that no longer suppresses anything is noise, and it silently \
+                hides new findings introduced on the lines it covers. Unused directives can be \
+                removed automatically with `scopelint fix`.",
+    bad_example:
+        "// scopelint: ignore-constant-next-line\nuint256 public constant MAX_SUPPLY = 1000;",
+    good_example:
+        "// scopelint: ignore-constant-next-line\nuint256 public constant maxSupply = 1000;",
+};
+
+/// Validates that every inline ignore directive suppresses at least one finding.
+///
+/// A directive is used once another validator reports an item in the range it covers, so this
+/// must run after all other validators have run on the file. Only `ignore-*` directives are
+/// checked, since `disable-*` directives also control formatting.
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+    parsed
+        .inline_config
+        .unused_ignore_directives()
+        .into_iter()
+        .map(|loc| {
+            let text = parsed.src[loc.start()..loc.end()].trim().to_string();
+            InvalidItem::new(Rule::UnusedDirective, parsed, loc, text)
+        })
+        .collect()
+}
+
+/// Removes the comments of the given unused directive items from the source.
+///
+/// A comment on a line of its own is removed together with the line, a trailing comment is removed
+/// along with the whitespace before it. Returns `None` if nothing was removed.
+#[must_use]
+pub fn fix_source(src: &str, items: &[&InvalidItem]) -> Option<String> {
+    let mut edits: Vec<(usize, usize)> = items
+        .iter()
+        .filter_map(|item| {
+            let start = line_column_to_offset(src, item.line, item.column)?;
+            let end = line_column_to_offset(src, item.end_line, item.end_column)?;
+            Some(expand_to_line(src, start, end))
+        })
+        .collect();
+    if edits.is_empty() {
+        return None;
+    }
+
+    // Apply the edits back to front so earlier offsets stay valid.
+    edits.sort_unstable();
+    edits.dedup();
+    let mut out = src.to_string();
+    for (start, end) in edits.into_iter().rev() {
+        out.replace_range(start..end, "");
+    }
+    Some(out)
+}
+
+/// Returns the range to remove for a comment spanning `start..end`: the whole line if the comment
+/// is the only thing on it, otherwise the comment and the whitespace before it.
+fn expand_to_line(src: &str, start: usize, end: usize) -> (usize, usize) {
+    let line_start = src[..start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = src[end..].find('\n').map_or(src.len(), |idx| end + idx);
+    let before = &src[line_start..start];
+    let after = &src[end..line_end];
+
+    if before.trim().is_empty() && after.trim().is_empty() {
+        (line_start, (line_end + 1).min(src.len()))
+    } else {
+        (start - (before.len() - before.trim_end().len()), end)
+    }
+}
+
+/// Converts a 1-based line and character column, as stored on an [`InvalidItem`], to a byte
+/// offset into the source.
+fn line_column_to_offset(src: &str, line: usize, column: usize) -> Option<usize> {
+    let line_start = if line == 1 {
+        0
+    } else {
+        src.match_indices('\n').nth(line - 2).map(|(idx, _)| idx + 1)?
+    };
+    let line_src = &src[line_start..];
+    let offset =
+        line_src.char_indices().map(|(idx, _)| idx).chain([line_src.len()]).nth(column - 1)?;
+    Some(line_start + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{parse_source, validators::constant_names::ConstantNames};
+    use std::path::Path;
+
+    /// Runs the constant name validator and then the unused directive validator on the source.
+    fn unused_directives(content: &str) -> Vec<InvalidItem> {
+        let parsed = parse_source(Path::new("./src/Counter.sol"), content).unwrap();
+        let _ = ConstantNames.validate(&parsed);
+        validate(&parsed)
+    }
+
+    #[test]
+    fn test_unused_directives() {
+        let content = r"
+contract Counter {
+  // scopelint: ignore-constant-next-line
+  uint256 constant bad = 1;
+  // scopelint: ignore-constant-next-line
+  uint256 constant GOOD = 1;
+  // scopelint: ignore-start
+  uint256 constant alsoBad = 1;
+  // scopelint: ignore-end
+  // scopelint: ignore-start
+  uint256 constant ALSO_GOOD = 1;
+  // scopelint: ignore-end
+  // scopelint: disable-next-line
+  uint256 constant STILL_GOOD = 1;
+}
+";
+        let items = unused_directives(content);
+        let lines: Vec<(usize, &str)> =
+            items.iter().map(|item| (item.line, item.text.as_str())).collect();
+        assert_eq!(
+            lines,
+            vec![
+                (5, "// scopelint: ignore-constant-next-line"),
+                (10, "// scopelint: ignore-start"),
+                (12, "// scopelint: ignore-end"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_directives() {
+        let content = r"
+contract Counter {
+  // scopelint: ignore-start
+  // scopelint: ignore-constant-next-line
+  uint256 constant bad = 1;
+  // scopelint: ignore-end
+}
+";
+        assert_eq!(unused_directives(content), vec![]);
+    }
+
+    #[test]
+    fn test_fix_source() {
+        let content = r"
+contract Counter {
+  // scopelint: ignore-constant-next-line
+  uint256 constant GOOD = 1;
+  uint256 constant ALSO_GOOD = 1; // scopelint: ignore-line
+  // scopelint: ignore-constant-next-line
+  uint256 constant bad = 1;
+}
+";
+        let items = unused_directives(content);
+        let items: Vec<&InvalidItem> = items.iter().collect();
+        let expected = r"
+contract Counter {
+  uint256 constant GOOD = 1;
+  uint256 constant ALSO_GOOD = 1;
+  // scopelint: ignore-constant-next-line
+  uint256 constant bad = 1;
+}
+";
+        assert_eq!(fix_source(content, &items).as_deref(), Some(expected));
+        assert_eq!(fix_source(expected, &[]), None);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::check::{
-    utils::{InvalidItem, ValidatorKind},
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, ValidatorKind},
+    validators::Validator,
     Parsed,
 };
 use regex::Regex;
@@ -17,8 +19,66 @@ static RE_IMPORT_SYMBOL_LIST_WITH_PATH: LazyLock<Regex> =
 static RE_IMPORT_ALIAS: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r#"import\s+"[^"]+"\s+as\s+(\w+);"#).unwrap());
 
-#[must_use]
 /// Validates that all imported symbols are actually used in the file.
+pub struct UnusedImports;
+
+impl Validator for UnusedImports {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Import
+    }
+
+    fn id(&self) -> &'static str {
+        "import"
+    }
+
+    fn description(&self) -> &'static str {
+        "Unused import"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[UNUSED_IMPORT]
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        None
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
+    }
+
+    fn fix(&self, parsed: &Parsed, items: &[&InvalidItem]) -> Option<String> {
+        let symbols: HashSet<String> =
+            items.iter().map(|item| extract_unused_import_symbol(&item.text)).collect();
+        fix_source(parsed, Some(&symbols))
+    }
+}
+
+/// The `unused-import` rule.
+const UNUSED_IMPORT: RuleInfo = RuleInfo {
+    rule: Rule::UnusedImport,
+    code: "SL801",
+    name: "unused-import",
+    summary: "Imported symbols must be used.",
+    rationale: "Unused imports make it harder to see what a file depends on and slow down \
+                compilation. They can be removed automatically with `scopelint fix`.",
+    bad_example: "import {ERC20, SafeERC20} from \"src/Tokens.sol\";\n\n\
+                  contract Vault is ERC20 {}",
+    good_example: "import {ERC20} from \"src/Tokens.sol\";\n\ncontract Vault is ERC20 {}",
+};
+
+/// Extracts the symbol name from an "Unused import: '`SymbolName`'" message.
+fn extract_unused_import_symbol(text: &str) -> String {
+    const PREFIX: &str = "Unused import: '";
+    const SUFFIX: char = '\'';
+    if let Some(stripped) = text.strip_prefix(PREFIX) {
+        if let Some(symbol) = stripped.strip_suffix(SUFFIX) {
+            return symbol.to_string();
+        }
+    }
+    text.to_string()
+}
+
 /// Reports unused imports that can be safely removed.
 ///
 /// This validator checks:
@@ -29,7 +89,7 @@ static RE_IMPORT_ALIAS: LazyLock<Regex> =
 /// # Panics
 ///
 /// Panics if regex captures are unexpectedly empty (should not happen with valid regex patterns).
-pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     let mut invalid_items: Vec<InvalidItem> = Vec::new();
     let mut imported_symbols: Vec<(String, usize, usize)> = Vec::new(); // (symbol_name, import_start, import_end)
     let mut import_ranges: Vec<(usize, usize)> = Vec::new();
@@ -78,7 +138,7 @@ pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
                 let offset = import_start + relative_pos;
                 let loc = solang_parser::pt::Loc::File(0, offset, offset + symbol_name.len());
                 invalid_items.push(InvalidItem::new(
-                    ValidatorKind::Import,
+                    Rule::UnusedImport,
                     parsed,
                     loc,
                     format!("Unused import: '{symbol_name}'"),
@@ -251,7 +311,7 @@ mod tests {
         "#;
 
         let expected_findings = ExpectedFindings::new(0);
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &UnusedImports);
     }
 
     #[test]
@@ -273,7 +333,7 @@ mod tests {
             handler: 1,
             script: 1,
         };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &UnusedImports);
     }
 
     #[test]
@@ -294,7 +354,7 @@ mod tests {
             handler: 1,
             script: 1,
         };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &UnusedImports);
     }
 
     #[test]
@@ -308,7 +368,7 @@ mod tests {
         "#;
 
         let expected_findings = ExpectedFindings::new(0);
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &UnusedImports);
     }
 
     #[test]
@@ -325,7 +385,7 @@ mod tests {
         "#;
 
         let expected_findings = ExpectedFindings::new(0);
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &UnusedImports);
     }
 
     #[test]
@@ -350,7 +410,7 @@ mod tests {
             handler: 1,
             script: 1,
         };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &UnusedImports);
     }
 
     fn parsed_from_src(content: &str) -> crate::check::Parsed {
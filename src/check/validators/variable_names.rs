@@ -1,29 +1,114 @@
 use crate::check::{
-    utils::{FileKind, InvalidItem, IsFileKind, ValidatorKind},
+    rules::{Rule, RuleInfo},
+    utils::{FileKind, InvalidItem, ValidatorKind},
+    validators::Validator,
     Parsed,
 };
 use solang_parser::pt::{
     ContractPart, FunctionDefinition, Parameter, SourceUnitPart, Statement, VariableDeclaration,
     VariableDefinition,
 };
-fn is_matching_file(parsed: &Parsed) -> bool {
-    let file = &parsed.file;
-    file.is_file_kind(FileKind::Src, &parsed.path_config) ||
-        file.is_file_kind(FileKind::Test, &parsed.path_config) ||
-        file.is_file_kind(FileKind::Handler, &parsed.path_config) ||
-        file.is_file_kind(FileKind::Script, &parsed.path_config)
+
+/// Validates that variable names follow the correct naming conventions.
+pub struct VariableNames;
+
+impl Validator for VariableNames {
+    fn kind(&self) -> ValidatorKind {
+        ValidatorKind::Variable
+    }
+
+    fn id(&self) -> &'static str {
+        "variable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Invalid variable name"
+    }
+
+    fn rules(&self) -> &'static [RuleInfo] {
+        &[
+            PARAMETER_NAME,
+            STORAGE_PARAMETER_NAME,
+            STATE_VARIABLE_NAME,
+            LOCAL_VARIABLE_NAME,
+            STORAGE_LOCAL_VARIABLE_NAME,
+        ]
+    }
+
+    fn file_kinds(&self) -> Option<&'static [FileKind]> {
+        Some(&[FileKind::Src, FileKind::Test, FileKind::Handler, FileKind::Script])
+    }
+
+    fn validate(&self, parsed: &Parsed) -> Vec<InvalidItem> {
+        validate(parsed)
+    }
 }
 
-#[must_use]
-/// Validates that variable names follow the correct naming conventions:
+/// The `parameter-name` rule.
+const PARAMETER_NAME: RuleInfo = RuleInfo {
+    rule: Rule::ParameterName,
+    code: "SL501",
+    name: "parameter-name",
+    summary: "Function parameters must be prefixed with an underscore.",
+    rationale: "Prefixing parameters with an underscore distinguishes them from state variables, \
+                which prevents accidentally shadowing or reading the wrong variable.",
+    bad_example: "function setNumber(uint256 newNumber) public {}",
+    good_example: "function setNumber(uint256 _newNumber) public {}",
+};
+
+/// The `storage-parameter-name` rule.
+const STORAGE_PARAMETER_NAME: RuleInfo = RuleInfo {
+    rule: Rule::StorageParameterName,
+    code: "SL502",
+    name: "storage-parameter-name",
+    summary: "Storage parameters must not be prefixed with an underscore.",
+    rationale: "Storage parameters are references to state, so like state variables they are not \
+                prefixed with an underscore. This makes writes to storage easy to spot.",
+    bad_example: "function _update(Position storage _position) internal {}",
+    good_example: "function _update(Position storage position) internal {}",
+};
+
+/// The `state-variable-name` rule.
+const STATE_VARIABLE_NAME: RuleInfo = RuleInfo {
+    rule: Rule::StateVariableName,
+    code: "SL503",
+    name: "state-variable-name",
+    summary: "State variables must not be prefixed with an underscore.",
+    rationale: "Only local variables and parameters are prefixed with an underscore, so any \
+                identifier without one refers to state and reading or writing it costs gas.",
+    bad_example: "uint256 internal _totalSupply;",
+    good_example: "uint256 internal totalSupply;",
+};
+
+/// The `local-variable-name` rule.
+const LOCAL_VARIABLE_NAME: RuleInfo = RuleInfo {
+    rule: Rule::LocalVariableName,
+    code: "SL504",
+    name: "local-variable-name",
+    summary: "Local variables must be prefixed with an underscore.",
+    rationale: "Prefixing local variables with an underscore distinguishes them from state \
+                variables, which prevents accidentally shadowing or writing to storage.",
+    bad_example: "uint256 balance = token.balanceOf(address(this));",
+    good_example: "uint256 _balance = token.balanceOf(address(this));",
+};
+
+/// The `storage-local-variable-name` rule.
+const STORAGE_LOCAL_VARIABLE_NAME: RuleInfo = RuleInfo {
+    rule: Rule::StorageLocalVariableName,
+    code: "SL505",
+    name: "storage-local-variable-name",
+    summary: "Local storage variables must not be prefixed with an underscore.",
+    rationale: "Local storage variables are references to state, so like state variables they are \
+                not prefixed with an underscore. This makes writes to storage easy to spot.",
+    bad_example: "Position storage _position = positions[_id];",
+    good_example: "Position storage position = positions[_id];",
+};
+
+/// Reports variables that do not follow the naming conventions:
 /// - Storage variables should NOT have an underscore prefix
 /// - Non-storage variables (local variables, parameters) should have an underscore prefix
 /// - Variables that reference storage/storages should NOT have an underscore prefix
-pub fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
-    if !is_matching_file(parsed) {
-        return Vec::new();
-    }
-
+fn validate(parsed: &Parsed) -> Vec<InvalidItem> {
     let mut invalid_items: Vec<InvalidItem> = Vec::new();
     for element in &parsed.pt.0 {
         match element {
@@ -68,12 +153,9 @@ fn validate_function(parsed: &Parsed, f: &FunctionDefinition) -> Vec<InvalidItem
                     } else {
                         format!("Parameter '{}' should have underscore prefix", &name.name)
                     };
-                    invalid_items.push(InvalidItem::new(
-                        ValidatorKind::Variable,
-                        parsed,
-                        p.loc,
-                        message,
-                    ));
+                    let rule =
+                        if is_storage { Rule::StorageParameterName } else { Rule::ParameterName };
+                    invalid_items.push(InvalidItem::new(rule, parsed, p.loc, message));
                 }
             }
         }
@@ -94,7 +176,7 @@ fn validate_state_variable(parsed: &Parsed, v: &VariableDefinition) -> Option<In
             None
         } else {
             Some(InvalidItem::new(
-                ValidatorKind::Variable,
+                Rule::StateVariableName,
                 parsed,
                 name.loc,
                 format!("State variable '{name_str}' should NOT have underscore prefix"),
@@ -122,12 +204,12 @@ fn validate_statement(parsed: &Parsed, stmt: &Statement) -> Vec<InvalidItem> {
                 } else {
                     format!("Local variable '{}' should have underscore prefix", &name.name)
                 };
-                invalid_items.push(InvalidItem::new(
-                    ValidatorKind::Variable,
-                    parsed,
-                    *loc,
-                    message,
-                ));
+                let rule = if is_storage {
+                    Rule::StorageLocalVariableName
+                } else {
+                    Rule::LocalVariableName
+                };
+                invalid_items.push(InvalidItem::new(rule, parsed, *loc, message));
             }
         }
         Statement::Block { statements, .. } => {
@@ -219,7 +301,7 @@ mod tests {
         ";
 
         let expected_findings = ExpectedFindings::new(0);
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &VariableNames);
     }
 
     #[test]
@@ -237,7 +319,7 @@ mod tests {
             script: 1,
             ..ExpectedFindings::default()
         };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &VariableNames);
     }
 
     #[test]
@@ -257,7 +339,7 @@ mod tests {
             script: 1,
             ..ExpectedFindings::default()
         };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &VariableNames);
     }
 
     #[test]
@@ -277,7 +359,7 @@ mod tests {
             script: 2,
             ..ExpectedFindings::default()
         };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &VariableNames);
     }
 
     #[test]
@@ -298,7 +380,7 @@ mod tests {
             script: 2,
             ..ExpectedFindings::default()
         };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &VariableNames);
     }
 
     #[test]
@@ -318,6 +400,6 @@ mod tests {
             script: 1,
             ..ExpectedFindings::default()
         };
-        expected_findings.assert_eq(content, &validate);
+        expected_findings.assert_eq(content, &VariableNames);
     }
 }
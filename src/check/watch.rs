@@ -0,0 +1,109 @@
+//! Watch mode for `scopelint check`.
+//!
+//! The watched files are polled for changes, and the check is re-run whenever a Solidity file in
+//! the `src`, `script` or `test` directories, or the `.scopelint` or `foundry.toml` file, is added,
+//! removed or modified. Every run checks the whole project, but the findings of unchanged files
+//! are reused from the [`cache`](crate::check::cache), so only changed files are validated again
+//! unless the cache is disabled.
+
+use super::{file_config::FileConfig, report::OutputFormat, walk_solidity_files, CheckOptions};
+use crate::{error::Result, foundry_config::CheckPaths};
+use colored::Colorize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// How often the watched files are polled for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification time and size of each watched file.
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+/// Runs the check, then re-runs it whenever a watched file changes. Never returns unless the
+/// watched files cannot be polled.
+/// # Errors
+/// Returns an error if the modification time of a watched file cannot be read.
+pub fn run(taplo_opts: &taplo::formatter::Options, check_opts: &CheckOptions) -> Result<()> {
+    let mut previous = None;
+    loop {
+        // Paths are loaded on every poll, so changing the directories in `foundry.toml` changes
+        // which files are watched.
+        // An invalid `foundry.toml` is reported by the check, and the default directories are
+        // watched until it is fixed.
+        let path_config = CheckPaths::load_from(&check_opts.root).unwrap_or_default();
+        let current = snapshot(&check_opts.root, check_opts.config_file.as_deref(), &path_config)?;
+        if previous.as_ref() != Some(&current) {
+            if check_opts.format == OutputFormat::Text && io::stderr().is_terminal() {
+                // Clear the screen so only the latest report is shown.
+                eprint!("\x1b[2J\x1b[H");
+            }
+            // Failed checks are expected while editing, and their output has been printed.
+            let _ = super::run(taplo_opts.clone(), check_opts);
+            eprintln!("{}: Watching for changes, press Ctrl-C to stop", "info".bold().green());
+            previous = Some(current);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Returns the modification time and size of the Solidity files in the `src`, `script` and `test`
+/// directories of the project at `root`, and of its `.scopelint` and `foundry.toml` files. The
+/// `.scopelint` file is `config_file` if given, otherwise the one found from `root`.
+fn snapshot(root: &Path, config_file: Option<&Path>, path_config: &CheckPaths) -> Result<Snapshot> {
+    let config_file = config_file
+        .map_or_else(|| FileConfig::find_config_file_from(root), |file| Some(file.to_path_buf()));
+    let config_files = [config_file, CheckPaths::find_foundry_toml_from(root)];
+    let files = path_config
+        .as_array()
+        .into_iter()
+        .flat_map(|dir| walk_solidity_files(&root.join(dir)).unwrap_or_default())
+        .chain(config_files.into_iter().flatten());
+
+    let mut snapshot = Snapshot::new();
+    for file in files {
+        // A file may be removed between walking the directory and reading its metadata, in which
+        // case it is missing from the snapshot.
+        let Ok(metadata) = fs::metadata(&file) else {
+            continue;
+        };
+        snapshot.insert(file, (metadata.modified()?, metadata.len()));
+    }
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TempDir;
+
+    #[test]
+    fn test_snapshot() {
+        let temp_dir = TempDir::new("watch-test");
+        let dir = temp_dir.path();
+        let src = dir.join("src");
+        fs::create_dir_all(&src).unwrap();
+        let path_config = CheckPaths {
+            src_path: "./src".to_string(),
+            script_path: "./missing".to_string(),
+            test_path: "./missing".to_string(),
+        };
+        let config_file = dir.join("custom.scopelint");
+        fs::write(&config_file, "").unwrap();
+        fs::write(src.join("A.sol"), "contract A {}").unwrap();
+        fs::write(src.join("README.md"), "Not watched").unwrap();
+
+        let before = snapshot(dir, Some(&config_file), &path_config).unwrap();
+        assert!(before.contains_key(&src.join("A.sol")));
+        assert!(!before.contains_key(&src.join("README.md")));
+        assert!(before.contains_key(&config_file));
+        assert_eq!(snapshot(dir, Some(&config_file), &path_config).unwrap(), before);
+
+        fs::write(src.join("A.sol"), "contract A { uint256 internal a; }").unwrap();
+        assert_ne!(snapshot(dir, Some(&config_file), &path_config).unwrap(), before);
+    }
+}
@@ -1,4 +1,9 @@
-use clap::{Parser, Subcommand};
+use crate::check::{
+    file_config::parse_rule_name, report::OutputFormat, utils::ValidatorKind,
+    validators::formatting,
+};
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand, ValueEnum};
+use std::path::{self, PathBuf};
 
 /// Returns version information with appropriate suffix
 fn version_info() -> &'static str {
@@ -21,17 +26,152 @@ fn version_info() -> &'static str {
 #[clap(version = version_info(), about, after_help = "Learn more: https://github.com/ScopeLift/scopelint")]
 /// Options for running scopelint.
 pub struct Opts {
+    #[clap(
+        long,
+        global = true,
+        value_name = "DIR",
+        value_parser = parse_dir,
+        help = "Run in the project at this directory instead of the current directory"
+    )]
+    /// The root directory of the project, which `foundry.toml`, `.scopelint` and all other paths
+    /// given to scopelint, including `--config`, are relative to. Defaults to the current
+    /// directory.
+    pub root: Option<PathBuf>,
+    #[clap(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "Use this .scopelint file instead of searching for one from the project root"
+    )]
+    /// The `.scopelint` file to use instead of the one found by searching up the directory tree
+    /// from the project root, relative to the root.
+    pub config: Option<PathBuf>,
     #[clap(subcommand)]
     /// The mode to run scopelint in.
     pub subcommand: Subcommands,
 }
 
+impl Opts {
+    /// Checks the combinations of arguments that clap cannot express, e.g. conflicts with a
+    /// specific value of an argument.
+    /// # Errors
+    /// Returns an error to print and exit with, like the errors of clap itself.
+    pub fn validate(&self) -> Result<(), clap::Error> {
+        if let Subcommands::Check { watch: true, format, .. } = &self.subcommand {
+            // Machine-readable documents would be printed one after another on every change.
+            if matches!(format, OutputFormat::Json | OutputFormat::Sarif | OutputFormat::Junit) {
+                let format = format.to_possible_value().map(|value| value.get_name().to_string());
+                return Err(Self::command().error(
+                    ErrorKind::ArgumentConflict,
+                    format!(
+                        "the argument '--watch' cannot be used with '--format {}'",
+                        format.unwrap_or_default()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Subcommand)]
 /// The mode to run scopelint in.
 pub enum Subcommands {
     #[clap(about = "Checks code to verify all conventions are being followed.")]
     /// Checks code to verify all conventions are being followed.
-    Check,
+    Check {
+        #[clap(long, value_enum, default_value_t, help = "Output format for findings")]
+        /// Output format for findings.
+        format: OutputFormat,
+        #[clap(long, help = "Do not show source code snippets in text output")]
+        /// Do not show source code snippets in text output.
+        no_snippets: bool,
+        #[clap(
+            long,
+            value_name = "FILE",
+            conflicts_with = "write_baseline",
+            help = "Only fail on findings that are not recorded in the baseline file"
+        )]
+        /// Only fail on findings that are not recorded in the baseline file.
+        baseline: Option<PathBuf>,
+        #[clap(
+            long,
+            value_name = "FILE",
+            help = "Record the current findings in a baseline file instead of failing on them"
+        )]
+        /// Record the current findings in a baseline file instead of failing on them.
+        write_baseline: Option<PathBuf>,
+        #[clap(
+            long,
+            value_name = "REF",
+            help = "Only report findings on lines changed relative to a git ref"
+        )]
+        /// Only report findings on lines changed relative to a git ref.
+        changed_since: Option<String>,
+        #[clap(
+            long,
+            requires = "changed_since",
+            help = "With --changed-since, report all findings in changed files"
+        )]
+        /// With `--changed-since`, report all findings in changed files instead of only those on
+        /// changed lines.
+        whole_files: bool,
+        #[clap(long, help = "Validate every file instead of reusing cached findings")]
+        /// Validate every file instead of reusing the cached findings of unchanged files.
+        no_cache: bool,
+        #[clap(
+            value_name = "PATH",
+            help = "Files, directories or globs to check instead of the whole project"
+        )]
+        /// Files, directories or globs to check instead of the `src`, `script` and `test`
+        /// directories.
+        paths: Vec<String>,
+        #[clap(
+            long,
+            requires = "stdin_filename",
+            conflicts_with = "paths",
+            help = "Check the source read from stdin instead of files on disk"
+        )]
+        /// Check the source read from stdin instead of files on disk.
+        stdin: bool,
+        #[clap(
+            long,
+            value_name = "FILE",
+            requires = "stdin",
+            help = "The path of the file whose source is read from stdin, e.g. src/Counter.sol"
+        )]
+        /// The path of the file whose source is read from stdin. It decides which rules apply to
+        /// the source, both by the kind of file and by `.scopelint`.
+        stdin_filename: Option<PathBuf>,
+        #[clap(
+            long,
+            conflicts_with_all = ["stdin", "write_baseline"],
+            help = "Re-run the check whenever a Solidity or config file changes"
+        )]
+        /// Re-run the check whenever a Solidity file, `.scopelint` or `foundry.toml` changes. Only
+        /// the `text` and `github` formats can be used, see [`Opts::validate`].
+        watch: bool,
+        #[clap(
+            long,
+            value_name = "RULE",
+            value_parser = parse_rule,
+            conflicts_with = "write_baseline",
+            help = "Only check this rule, e.g. test or fmt (can be repeated)"
+        )]
+        /// Only check these rules, by the names used in `.scopelint`, or `fmt` for the formatting
+        /// check.
+        only: Vec<String>,
+        #[clap(
+            long,
+            value_name = "RULE",
+            value_parser = parse_rule,
+            conflicts_with = "write_baseline",
+            help = "Do not check this rule, e.g. import or fmt (can be repeated)"
+        )]
+        /// Do not check these rules, by the names used in `.scopelint`, or `fmt` for the
+        /// formatting check.
+        skip: Vec<String>,
+    },
     #[clap(about = "Formats Solidity and TOML files in the codebase.")]
     /// Formats Solidity and TOML files in the codebase.
     Fmt {
@@ -41,7 +181,40 @@ pub enum Subcommands {
     },
     #[clap(about = "Applies safe fixes (e.g. remove unused imports), then runs check.")]
     /// Applies safe fixes (e.g. remove unused imports), then runs check.
-    Fix,
+    Fix {
+        #[clap(
+            value_name = "PATH",
+            help = "Files, directories or globs to fix instead of the whole project"
+        )]
+        /// Files, directories or globs to fix instead of the `src`, `script` and `test`
+        /// directories.
+        paths: Vec<String>,
+        #[clap(
+            long,
+            value_name = "RULE",
+            value_parser = parse_rule,
+            help = "Only fix and check this rule, e.g. import or fmt (can be repeated)"
+        )]
+        /// Only fix and check these rules, by the names used in `.scopelint`, or `fmt` for the
+        /// formatting check.
+        only: Vec<String>,
+        #[clap(
+            long,
+            value_name = "RULE",
+            value_parser = parse_rule,
+            help = "Do not fix or check this rule, e.g. directive or fmt (can be repeated)"
+        )]
+        /// Do not fix or check these rules, by the names used in `.scopelint`, or `fmt` for the
+        /// formatting check.
+        skip: Vec<String>,
+    },
+    #[clap(about = "Explains a rule, or lists all rules if no code is given.")]
+    /// Explains a rule, or lists all rules if no code is given.
+    Explain {
+        #[clap(help = "The code of the rule to explain, e.g. SL201")]
+        /// The code of the rule to explain, e.g. `SL201`.
+        code: Option<String>,
+    },
     #[clap(about = "Generates a specification for the current project from test names.")]
     /// Generates a specification for the current project from test names.
     Spec {
@@ -49,4 +222,27 @@ pub enum Subcommands {
         /// Show internal functions in the specification.
         show_internal: bool,
     },
+    #[clap(about = "Runs a language server over stdio that reports findings in editors.")]
+    /// Runs a language server over stdio that reports findings in editors.
+    Lsp,
+}
+
+/// Parses the directory given to `--root` into an absolute path.
+fn parse_dir(dir: &str) -> Result<PathBuf, String> {
+    let dir = path::absolute(dir).map_err(|err| err.to_string())?;
+    if !dir.is_dir() {
+        return Err("not a directory".to_string());
+    }
+    Ok(dir)
+}
+
+/// Parses a rule name given to `--only` or `--skip`: a rule name used in `.scopelint`, or `fmt`
+/// for the formatting check.
+fn parse_rule(rule: &str) -> Result<String, String> {
+    if rule == formatting::RULE || parse_rule_name(rule).is_some() {
+        return Ok(rule.to_string());
+    }
+    let names: Vec<&str> =
+        ValidatorKind::all().iter().map(ValidatorKind::name).chain([formatting::RULE]).collect();
+    Err(format!("unknown rule, expected one of: {}", names.join(", ")))
 }
@@ -0,0 +1,106 @@
+//! The error type returned by scopelint.
+//!
+//! Each variant maps to a distinct process exit code, see [`Error::exit_code`], so scripts can
+//! tell failed checks apart from a broken setup.
+
+use crate::check::{report::Report, ParseError};
+use std::{fmt, io};
+
+/// A specialized [`Result`](std::result::Result) type for scopelint operations.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The errors returned by scopelint.
+#[derive(Debug)]
+pub enum Error {
+    /// A file or directory could not be read or written.
+    Io(io::Error),
+    /// A Solidity file could not be parsed.
+    Parse(ParseError),
+    /// A configuration file or command line argument is invalid.
+    Config(String),
+    /// An external tool, such as `forge` or `git`, could not be run or failed.
+    ExternalTool(String),
+    /// The checks ran, but found invalid items or unformatted files. The report has the findings
+    /// of the convention checks, and none if only the formatting check failed.
+    ChecksFailed(Report),
+}
+
+impl Error {
+    /// Returns an [`Error::Io`] with the given context, e.g. the file that could not be read,
+    /// prepended to the message.
+    pub(crate) fn io(context: impl fmt::Display, err: &io::Error) -> Self {
+        Self::Io(io::Error::new(err.kind(), format!("{context}: {err}")))
+    }
+
+    /// Returns the process exit code for the error.
+    #[must_use]
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::ChecksFailed(_) => 1,
+            Self::Config(_) => 2,
+            Self::Io(_) => 3,
+            Self::Parse(_) => 4,
+            Self::ExternalTool(_) => 5,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Parse(err) => write!(f, "{err}"),
+            Self::Config(message) | Self::ExternalTool(message) => write!(f, "{message}"),
+            Self::ChecksFailed(_) => write!(f, "One or more checks failed"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            Self::Config(_) | Self::ExternalTool(_) | Self::ChecksFailed(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<walkdir::Error> for Error {
+    fn from(err: walkdir::Error) -> Self {
+        Self::Io(err.into())
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            Error::ChecksFailed(Report::default()),
+            Error::Config(String::new()),
+            Error::Io(io::Error::other("")),
+            Error::Parse(ParseError { file: "./src/A.sol".into(), diagnostics: Vec::new() }),
+            Error::ExternalTool(String::new()),
+        ];
+        let codes: Vec<i32> = errors.iter().map(Error::exit_code).collect();
+        assert_eq!(codes, vec![1, 2, 3, 4, 5]);
+
+        let err = Error::io("Failed to read ./foundry.toml", &io::Error::other("denied"));
+        assert_eq!(err.to_string(), "Failed to read ./foundry.toml: denied");
+    }
+}
@@ -1,25 +1,30 @@
+use crate::{
+    check::{log_error, report::Report},
+    error::{Error, Result},
+};
 use colored::Colorize;
-use std::{error::Error, fs, process};
+use std::{fs, path::Path, process};
 
-/// Check formatting without modifying files.
+/// Check formatting of the project at `root` without modifying files.
 /// # Errors
-/// Errors if `forge fmt` fails, or if `taplo` fails to format `foundry.toml`.
-fn check_formatting(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
+/// Errors if `forge fmt` fails, or if `taplo` fails to format `foundry.toml`. Returns
+/// [`Error::ChecksFailed`] if any file would be reformatted.
+fn check_formatting(taplo_opts: taplo::formatter::Options, root: &Path) -> Result<()> {
     println!("Checking formatting...");
 
-    let forge_status = process::Command::new("forge").args(["fmt", "--check"]).output()?;
+    let forge_status = run_forge(&["fmt", "--check"], root)?;
 
     let mut has_changes = false;
 
     // Print any warnings/errors from `forge fmt --check`.
     if !forge_status.stderr.is_empty() {
-        print!("{}", String::from_utf8(forge_status.stderr)?);
+        print!("{}", String::from_utf8_lossy(&forge_status.stderr));
     }
 
     // Print the diff output from forge fmt --check with colors
     if !forge_status.stdout.is_empty() {
         println!("Solidity files that would be reformatted:");
-        let forge_output = String::from_utf8(forge_status.stdout)?;
+        let forge_output = String::from_utf8_lossy(&forge_status.stdout);
 
         for line in forge_output.lines() {
             if line.starts_with("Diff in ") {
@@ -53,7 +58,7 @@ fn check_formatting(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn
     }
 
     // Check foundry.toml formatting
-    let config_orig = fs::read_to_string("./foundry.toml")?;
+    let config_orig = read_foundry_toml(root)?;
     let config_fmt = taplo::formatter::format(&config_orig, taplo_opts);
 
     if config_orig != config_fmt {
@@ -79,42 +84,60 @@ fn check_formatting(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn
         has_changes = true;
     }
 
-    // Exit with error code if any files would be changed
+    // Fail if any files would be changed
     if has_changes {
         println!("\nRun 'scopelint fmt' to apply these changes.");
-        process::exit(1);
-    } else {
-        println!("All files are properly formatted!");
+        return Err(Error::ChecksFailed(Report::default()));
     }
-
+    println!("All files are properly formatted!");
     Ok(())
 }
 
-/// Apply formatting to files.
+/// Apply formatting to the files of the project at `root`.
 /// # Errors
 /// Errors if `forge fmt` fails, or if `taplo` fails to format `foundry.toml`.
-fn apply_formatting(taplo_opts: taplo::formatter::Options) -> Result<(), Box<dyn Error>> {
-    let forge_status = process::Command::new("forge").arg("fmt").output()?;
+fn apply_formatting(taplo_opts: taplo::formatter::Options, root: &Path) -> Result<()> {
+    let forge_status = run_forge(&["fmt"], root)?;
 
     // Print any warnings/errors from `forge fmt`.
     if !forge_status.stderr.is_empty() {
-        print!("{}", String::from_utf8(forge_status.stderr)?);
+        print!("{}", String::from_utf8_lossy(&forge_status.stderr));
     }
 
     // Format `foundry.toml` with taplo.
-    let config_orig = fs::read_to_string("./foundry.toml")?;
+    let config_orig = read_foundry_toml(root)?;
     let config_fmt = taplo::formatter::format(&config_orig, taplo_opts);
-    fs::write("./foundry.toml", config_fmt)?;
+    let config_path = root.join("foundry.toml");
+    fs::write(&config_path, config_fmt).map_err(|err| {
+        log_error(Error::io(format!("Failed to write {}", config_path.display()), &err))
+    })?;
     Ok(())
 }
 
-/// Format the code.
+/// Runs `forge` with the given arguments in the directory `root` and returns its output.
+fn run_forge(args: &[&str], root: &Path) -> Result<process::Output> {
+    process::Command::new("forge")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|err| log_error(Error::ExternalTool(format!("Failed to run forge: {err}"))))
+}
+
+/// Reads `foundry.toml` from the directory `root`.
+fn read_foundry_toml(root: &Path) -> Result<String> {
+    let config_path = root.join("foundry.toml");
+    fs::read_to_string(&config_path).map_err(|err| {
+        log_error(Error::io(format!("Failed to read {}", config_path.display()), &err))
+    })
+}
+
+/// Format the code of the project at `root`.
 /// # Errors
 /// Errors if `forge fmt` fails, or if `taplo` fails to format `foundry.toml`.
-pub fn run(taplo_opts: taplo::formatter::Options, check: bool) -> Result<(), Box<dyn Error>> {
+pub fn run(taplo_opts: taplo::formatter::Options, check: bool, root: &Path) -> Result<()> {
     if check {
-        check_formatting(taplo_opts)
+        check_formatting(taplo_opts, root)
     } else {
-        apply_formatting(taplo_opts)
+        apply_formatting(taplo_opts, root)
     }
 }
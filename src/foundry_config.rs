@@ -4,7 +4,8 @@
 //! (e.g. `contracts/` instead of `src/`). Paths can be overridden with a
 //! scopelint-specific `[check]` section.
 
-use std::path::PathBuf;
+use crate::error::{Error, Result};
+use std::path::{Path, PathBuf};
 
 /// Paths for source, script, and test directories (relative to project root).
 /// Normalized to start with `./` for consistent use with walking and path checks.
@@ -35,24 +36,26 @@ impl CheckPaths {
         [self.src_path.as_str(), self.script_path.as_str(), self.test_path.as_str()]
     }
 
-    /// Load paths from `foundry.toml`: use `[check]` overrides if present,
-    /// otherwise `[profile.default]` (or root-level) `src`, `test`, `script`.
-    /// Returns default paths if no config is found or parsing fails.
-    #[must_use]
-    pub fn load() -> Self {
-        let Some(config_path) = Self::find_foundry_toml() else {
-            return Self::default();
-        };
-
-        let Ok(content) = std::fs::read_to_string(&config_path) else {
-            return Self::default();
+    /// Load paths from the `foundry.toml` of the project at `root`, searching up the directory
+    /// tree from `root`: use `[check]` overrides if present, otherwise `[profile.default]` (or
+    /// root-level) `src`, `test`, `script`. Returns default paths if no config is found.
+    /// # Errors
+    /// Returns an error if the config can't be read or parsed.
+    pub fn load_from(root: &Path) -> Result<Self> {
+        let Some(config_path) = Self::find_foundry_toml_from(root) else {
+            return Ok(Self::default());
         };
 
-        Self::from_toml(&content).unwrap_or_default()
+        let content = std::fs::read_to_string(&config_path)
+            .map_err(|err| Error::io(format!("Failed to read {}", config_path.display()), &err))?;
+        Self::from_toml(&content).map_err(|err| {
+            Error::Config(format!("Failed to parse {}: {err}", config_path.display()))
+        })
     }
 
-    fn find_foundry_toml() -> Option<PathBuf> {
-        let mut current_dir = std::env::current_dir().ok()?;
+    /// Search up the directory tree from `dir` to find `foundry.toml`.
+    pub(crate) fn find_foundry_toml_from(dir: &Path) -> Option<PathBuf> {
+        let mut current_dir = dir.to_path_buf();
 
         loop {
             let config_path = current_dir.join("foundry.toml");
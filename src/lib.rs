@@ -2,26 +2,38 @@
 #![warn(unreachable_pub, unused, rust_2021_compatibility)]
 #![warn(clippy::all, clippy::pedantic, clippy::cargo, clippy::nursery)]
 #![allow(clippy::multiple_crate_versions)]
-use std::error::Error;
 
 /// Runs validators on Solidity files.
 pub mod check;
 
 /// Parses library configuration.
+#[cfg(feature = "cli")]
 pub mod config;
 
+/// The error type returned by scopelint.
+pub mod error;
+
 /// Path configuration from foundry.toml.
 pub mod foundry_config;
 
+/// Runs a language server that reports findings in editors.
+#[cfg(feature = "cli")]
+pub mod lsp;
+
 /// Parses Solidity source files.
 pub mod parser;
 
 /// Formats Solidity and TOML files.
+#[cfg(feature = "fmt")]
 pub mod fmt;
 
 /// Generates a specification for the current project from test names.
+#[cfg(feature = "spec")]
 pub mod spec;
 
+pub use check::linter::{Linter, LinterConfig};
+pub use error::{Error, Result};
+
 // ===========================
 // ======== Execution ========
 // ===========================
@@ -29,7 +41,8 @@ pub mod spec;
 /// Takes the provided `opts` and runs the program.
 /// # Errors
 /// Errors if the provided mode fails to run.
-pub fn run(opts: &config::Opts) -> Result<(), Box<dyn Error>> {
+#[cfg(feature = "cli")]
+pub fn run(opts: &config::Opts) -> Result<()> {
     // Configure formatting options, https://taplo.tamasfe.dev/.
     let taplo_opts = taplo::formatter::Options {
         allowed_blank_lines: 1,
@@ -38,11 +51,70 @@ pub fn run(opts: &config::Opts) -> Result<(), Box<dyn Error>> {
         ..Default::default()
     };
 
+    // Resolve the project, see `config::Opts::root`.
+    let root = match &opts.root {
+        Some(root) => root.clone(),
+        None => std::env::current_dir().map_err(|err| check::log_error(err.into()))?,
+    };
+    let config_file = match &opts.config {
+        Some(file) if !root.join(file).is_file() => {
+            let err = Error::Config(format!("Config file {} does not exist", file.display()));
+            return Err(check::log_error(err));
+        }
+        file => file.as_ref().map(|file| root.join(file)),
+    };
+
     // Execute commands.
     match &opts.subcommand {
-        config::Subcommands::Check => check::run(taplo_opts),
-        config::Subcommands::Fmt { check } => fmt::run(taplo_opts, *check),
-        config::Subcommands::Fix => check::run_fix(taplo_opts),
-        config::Subcommands::Spec { show_internal } => spec::run(*show_internal),
+        config::Subcommands::Check {
+            format,
+            no_snippets,
+            baseline,
+            write_baseline,
+            changed_since,
+            whole_files,
+            no_cache,
+            paths,
+            stdin: _,
+            stdin_filename,
+            watch,
+            only,
+            skip,
+        } => {
+            let check_opts = check::CheckOptions {
+                format: *format,
+                show_snippets: !no_snippets,
+                baseline: baseline.clone(),
+                write_baseline: write_baseline.clone(),
+                changed_since: changed_since.clone(),
+                whole_files: *whole_files,
+                use_cache: !no_cache,
+                paths: paths.clone(),
+                stdin_filename: stdin_filename.clone(),
+                rules: check::RuleFilter { only: only.clone(), skip: skip.clone() },
+                root,
+                config_file,
+            };
+            if *watch {
+                check::watch::run(&taplo_opts, &check_opts)
+            } else {
+                check::run(taplo_opts, &check_opts)
+            }
+        }
+        config::Subcommands::Fmt { check } => fmt::run(taplo_opts, *check, &root),
+        config::Subcommands::Fix { paths, only, skip } => {
+            let check_opts = check::CheckOptions {
+                show_snippets: true,
+                paths: paths.clone(),
+                rules: check::RuleFilter { only: only.clone(), skip: skip.clone() },
+                root,
+                config_file,
+                ..check::CheckOptions::default()
+            };
+            check::run_fix(taplo_opts, &check_opts)
+        }
+        config::Subcommands::Explain { code } => check::explain(code.as_deref()),
+        config::Subcommands::Spec { show_internal } => spec::run(*show_internal, &root),
+        config::Subcommands::Lsp => lsp::run(&root, config_file.as_deref()),
     }
 }
@@ -0,0 +1,375 @@
+//! Language server that reports findings in editors.
+//!
+//! `scopelint lsp` speaks the Language Server Protocol over stdio. Whenever a Solidity document is
+//! opened or changed, the validators run on its current content and the findings are published as
+//! diagnostics. Findings that a validator can fix are offered as quick fix code actions.
+//!
+//! The configuration is loaded from `.scopelint` and `foundry.toml` relative to the project root,
+//! which is the directory the server is started in unless `--root` is given. Editors set it to the
+//! workspace root. The server does not start if the configuration is invalid, and while it runs,
+//! invalid edits to it are logged to stderr and no findings are reported until they are fixed.
+
+use crate::check::{
+    self,
+    linter::{Linter, LinterConfig},
+    utils::{offset_to_line_column, InvalidItem, Severity},
+    validators::VALIDATORS,
+};
+use itertools::Itertools;
+use lsp_server::{Connection, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as NotificationTrait, PublishDiagnostics,
+    },
+    request::{CodeActionRequest, Request as RequestTrait},
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, NumberOrString, Position,
+    PublishDiagnosticsParams, Range, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextEdit, Url, WorkspaceEdit,
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The project the documents are checked in.
+struct Workspace {
+    /// The project root.
+    root: PathBuf,
+    /// The `.scopelint` file to use instead of the one found from the root.
+    config_file: Option<PathBuf>,
+}
+
+impl Workspace {
+    /// Returns a linter for the project. The configuration is loaded on every request, so edits to
+    /// `.scopelint` and `foundry.toml` apply without restarting the server.
+    fn linter(&self) -> crate::Result<Linter> {
+        let config = LinterConfig::load_with_config_file(&self.root, self.config_file.as_deref())
+            .map_err(check::log_error)?;
+        Ok(Linter::new(self.root.clone(), config))
+    }
+}
+
+/// Runs the language server on stdio until the client shuts it down, checking documents in the
+/// project at `root`, optionally with the given `.scopelint` file.
+/// # Errors
+/// Returns an error if the configuration is invalid or the connection to the client fails.
+pub fn run(root: &Path, config_file: Option<&Path>) -> crate::Result<()> {
+    let workspace =
+        Workspace { root: root.to_path_buf(), config_file: config_file.map(Path::to_path_buf) };
+    workspace.linter()?;
+    let (connection, io_threads) = Connection::stdio();
+    serve(&connection, &workspace)
+        .map_err(|err| check::log_error(crate::Error::Io(io::Error::other(err.to_string()))))?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Handles the messages of a client on the connection until it shuts the server down.
+fn serve(connection: &Connection, workspace: &Workspace) -> Result<(), Box<dyn Error>> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        ..ServerCapabilities::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    // The content of the open documents, which may not be saved yet.
+    let mut documents: HashMap<Url, String> = HashMap::new();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                let response = handle_request(workspace, &documents, request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                let Some(uri) = handle_notification(&mut documents, notification) else {
+                    continue;
+                };
+                let diagnostics = documents
+                    .get(&uri)
+                    .and_then(|src| {
+                        let linter = workspace.linter().ok()?;
+                        let items = check_document(&linter, &uri, src);
+                        Some(items.iter().map(|item| diagnostic(src, item)).collect())
+                    })
+                    .unwrap_or_default();
+                let params = PublishDiagnosticsParams { uri, diagnostics, version: None };
+                connection.sender.send(Message::Notification(Notification::new(
+                    PublishDiagnostics::METHOD.to_string(),
+                    params,
+                )))?;
+            }
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Updates the open documents. Returns the document whose diagnostics must be published, if any.
+fn handle_notification(
+    documents: &mut HashMap<Url, String>,
+    notification: Notification,
+) -> Option<Url> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: DidOpenTextDocumentParams =
+                serde_json::from_value(notification.params).ok()?;
+            let document = params.text_document;
+            documents.insert(document.uri.clone(), document.text);
+            Some(document.uri)
+        }
+        DidChangeTextDocument::METHOD => {
+            let params: DidChangeTextDocumentParams =
+                serde_json::from_value(notification.params).ok()?;
+            // Documents are synced in full, so the last change contains the whole content.
+            let change = params.content_changes.into_iter().last()?;
+            let uri = params.text_document.uri;
+            documents.insert(uri.clone(), change.text);
+            Some(uri)
+        }
+        DidCloseTextDocument::METHOD => {
+            let params: DidCloseTextDocumentParams =
+                serde_json::from_value(notification.params).ok()?;
+            documents.remove(&params.text_document.uri);
+            Some(params.text_document.uri)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the response to a request of the client.
+fn handle_request(
+    workspace: &Workspace,
+    documents: &HashMap<Url, String>,
+    request: Request,
+) -> Response {
+    if request.method != CodeActionRequest::METHOD {
+        return Response::new_err(
+            request.id,
+            lsp_server::ErrorCode::MethodNotFound as i32,
+            format!("Unsupported request: {}", request.method),
+        );
+    }
+    match serde_json::from_value::<CodeActionParams>(request.params) {
+        Ok(params) => Response::new_ok(request.id, code_actions(workspace, documents, &params)),
+        Err(err) => Response::new_err(
+            request.id,
+            lsp_server::ErrorCode::InvalidParams as i32,
+            err.to_string(),
+        ),
+    }
+}
+
+/// Returns a quick fix for each validator that can fix findings in the requested range.
+fn code_actions(
+    workspace: &Workspace,
+    documents: &HashMap<Url, String>,
+    params: &CodeActionParams,
+) -> Vec<CodeActionOrCommand> {
+    let uri = &params.text_document.uri;
+    let (Some(src), Ok(linter)) = (documents.get(uri), workspace.linter()) else {
+        return Vec::new();
+    };
+    let items = check_document(&linter, uri, src);
+    let Some(Ok(parsed)) = document_path(uri).map(|file| linter.parse(&file, src)) else {
+        return Vec::new();
+    };
+
+    let mut actions = Vec::new();
+    for validator in &VALIDATORS {
+        let items: Vec<&InvalidItem> = items
+            .iter()
+            .filter(|item| item.kind == validator.kind())
+            .filter(|item| {
+                let range = item_range(src, item);
+                range.start <= params.range.end && params.range.start <= range.end
+            })
+            .collect();
+        if items.is_empty() {
+            continue;
+        }
+        let Some(new_src) = validator.fix(&parsed, &items) else {
+            continue;
+        };
+
+        let edit = TextEdit {
+            range: Range::new(Position::new(0, 0), end_position(src)),
+            new_text: new_src,
+        };
+        actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title: format!("Fix {}", items.iter().map(|item| item.rule.name()).unique().join(", ")),
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(items.iter().map(|item| diagnostic(src, item)).collect()),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+                ..WorkspaceEdit::default()
+            }),
+            ..CodeAction::default()
+        }));
+    }
+    actions
+}
+
+/// Runs all validators on the content of a document and returns the findings that are reported.
+/// Documents that are not files, or are ignored in `.scopelint`, have no findings.
+fn check_document(linter: &Linter, uri: &Url, src: &str) -> Vec<InvalidItem> {
+    let Some(file) = document_path(uri) else {
+        return Vec::new();
+    };
+    linter.check_source(&file, src).into_iter().filter(|item| !item.is_suppressed()).collect()
+}
+
+/// Returns the path of a document, or `None` if it is not a file.
+fn document_path(uri: &Url) -> Option<PathBuf> {
+    uri.to_file_path().ok()
+}
+
+/// Converts an invalid item to a diagnostic.
+fn diagnostic(src: &str, item: &InvalidItem) -> Diagnostic {
+    Diagnostic {
+        range: item_range(src, item),
+        severity: Some(match item.severity {
+            Severity::Warn => DiagnosticSeverity::WARNING,
+            Severity::Error | Severity::Off => DiagnosticSeverity::ERROR,
+        }),
+        code: Some(NumberOrString::String(item.rule.code().to_string())),
+        source: Some("scopelint".to_string()),
+        message: format!("{}: {}", item.rule.name(), item.text),
+        ..Diagnostic::default()
+    }
+}
+
+/// Returns the range of the source an invalid item spans.
+fn item_range(src: &str, item: &InvalidItem) -> Range {
+    Range::new(position(src, item.line, item.column), position(src, item.end_line, item.end_column))
+}
+
+/// Returns the position just past the end of the source.
+fn end_position(src: &str) -> Position {
+    let (line, column) = offset_to_line_column(src, src.len());
+    position(src, line, column)
+}
+
+/// Converts a 1-based line and character column, as stored on an [`InvalidItem`], to an LSP
+/// position, whose character offset is counted in UTF-16 code units.
+fn position(src: &str, line: usize, column: usize) -> Position {
+    let text = src.lines().nth(line - 1).unwrap_or_default();
+    let character: usize = text.chars().take(column - 1).map(char::len_utf16).sum();
+    let to_u32 = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+    Position::new(to_u32(line - 1), to_u32(character))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TempDir;
+    use lsp_types::{
+        notification::Exit, request::Initialize, CodeActionContext, InitializeParams,
+        TextDocumentIdentifier, TextDocumentItem,
+    };
+    use std::{fs, thread};
+
+    const SRC: &str = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\n\
+                       import {Unused} from \"./Unused.sol\";\n\ncontract Foo {\n  \
+                       uint256 public constant badConstant = 1; // ✓\n}\n";
+
+    #[test]
+    fn test_position() {
+        assert_eq!(position("a\n  ✓b", 2, 4), Position::new(1, 3));
+        assert_eq!(position("a\n😀b", 2, 2), Position::new(1, 2));
+        assert_eq!(end_position("a\nbc\n"), Position::new(2, 0));
+    }
+
+    #[test]
+    fn test_serve() {
+        // A project whose `.scopelint` turns unused imports into warnings.
+        let temp_dir = TempDir::new("lsp-test");
+        let root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("foundry.toml"), "[profile.default]\nsrc = \"src\"\n").unwrap();
+        fs::write(root.join(".scopelint"), "[rules]\nimport = \"warn\"\n").unwrap();
+
+        let (server, client) = Connection::memory();
+        let uri = Url::from_file_path(root.join("src/Foo.sol")).unwrap();
+        let workspace = Workspace { root, config_file: None };
+        let server = thread::spawn(move || serve(&server, &workspace).unwrap());
+
+        let send_request = |id: i32, method: &str, params: serde_json::Value| {
+            let request = Request::new(id.into(), method.to_string(), params);
+            client.sender.send(Message::Request(request)).unwrap();
+        };
+        let send_notification = |method: &str, params: serde_json::Value| {
+            let notification = Notification::new(method.to_string(), params);
+            client.sender.send(Message::Notification(notification)).unwrap();
+        };
+
+        send_request(
+            1,
+            Initialize::METHOD,
+            serde_json::to_value(InitializeParams::default()).unwrap(),
+        );
+        assert!(matches!(client.receiver.recv().unwrap(), Message::Response(_)));
+        send_notification("initialized", serde_json::json!({}));
+
+        let document =
+            TextDocumentItem::new(uri.clone(), "solidity".to_string(), 1, SRC.to_string());
+        send_notification(
+            DidOpenTextDocument::METHOD,
+            serde_json::to_value(DidOpenTextDocumentParams { text_document: document }).unwrap(),
+        );
+        let Message::Notification(notification) = client.receiver.recv().unwrap() else {
+            panic!("expected diagnostics");
+        };
+        let params: PublishDiagnosticsParams = serde_json::from_value(notification.params).unwrap();
+        let diagnostics: Vec<(String, Range, Option<DiagnosticSeverity>)> = params
+            .diagnostics
+            .into_iter()
+            .map(|diagnostic| (diagnostic.message, diagnostic.range, diagnostic.severity))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    "constant-name: badConstant".to_string(),
+                    Range::new(Position::new(6, 26), Position::new(6, 37)),
+                    Some(DiagnosticSeverity::ERROR)
+                ),
+                (
+                    "unused-import: Unused import: 'Unused'".to_string(),
+                    Range::new(Position::new(3, 8), Position::new(3, 14)),
+                    Some(DiagnosticSeverity::WARNING)
+                ),
+            ]
+        );
+
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            range: Range::new(Position::new(3, 10), Position::new(3, 10)),
+            context: CodeActionContext::default(),
+            work_done_progress_params: lsp_types::WorkDoneProgressParams::default(),
+            partial_result_params: lsp_types::PartialResultParams::default(),
+        };
+        send_request(2, CodeActionRequest::METHOD, serde_json::to_value(params).unwrap());
+        let Message::Response(response) = client.receiver.recv().unwrap() else {
+            panic!("expected code actions");
+        };
+        let actions: Vec<CodeAction> = serde_json::from_value(response.result.unwrap()).unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Fix unused-import");
+        let edits = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert!(!edits[0].new_text.contains("Unused"));
+
+        send_request(3, "shutdown", serde_json::Value::Null);
+        assert!(matches!(client.receiver.recv().unwrap(), Message::Response(_)));
+        send_notification(Exit::METHOD, serde_json::Value::Null);
+        server.join().unwrap();
+    }
+}
@@ -8,9 +8,12 @@ use std::process;
 
 fn main() {
     let opts = Opts::parse();
+    if let Err(err) = opts.validate() {
+        err.exit();
+    }
 
-    if let Err(_err) = scopelint::run(&opts) {
+    if let Err(err) = scopelint::run(&opts) {
         // All warnings/errors have already been logged.
-        process::exit(1);
+        process::exit(err.exit_code());
     }
 }
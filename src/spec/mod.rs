@@ -4,7 +4,12 @@
 #![allow(clippy::case_sensitive_file_extension_comparisons)]
 
 use crate::{
-    check::utils::{Name, VisibilitySummary},
+    check::{
+        log_error,
+        utils::{Name, VisibilitySummary},
+        ParseError,
+    },
+    error::{Error, Result},
     foundry_config::CheckPaths,
 };
 use colored::Colorize;
@@ -12,27 +17,30 @@ use solang_parser::pt::{
     ContractDefinition, ContractPart, ContractTy, FunctionDefinition, SourceUnitPart,
 };
 use std::{
-    error::Error,
     fs,
     path::{Path, PathBuf},
 };
 use walkdir::WalkDir;
 
-/// Generates a specification for the current project from test names.
+/// Generates a specification for the project at `root` from test names.
 /// # Errors
-/// Returns an error if the specification could not be generated from the Solidity code.
+/// Returns an error if a Solidity file could not be read or parsed.
 /// # Panics
 /// Panics when a file path could not be unwrapped.
-pub fn run(show_internal: bool) -> Result<(), Box<dyn Error>> {
+pub fn run(show_internal: bool, root: &Path) -> Result<()> {
     // =================================
     // ======== Parse contracts ========
     // =================================
 
     // First, parse all source and test files to collect the contracts and their methods. All free
     // functions are added under a special contract called `FreeFunctions`.
-    let path_config = CheckPaths::load();
-    let src_contracts = get_contracts_for_dir(&path_config.src_path, ".sol", show_internal);
-    let test_contracts = get_contracts_for_dir(&path_config.test_path, ".t.sol", show_internal);
+    let path_config = CheckPaths::load_from(root).map_err(log_error)?;
+    let src_contracts =
+        get_contracts_for_dir(root.join(&path_config.src_path), ".sol", show_internal)
+            .map_err(log_error)?;
+    let test_contracts =
+        get_contracts_for_dir(root.join(&path_config.test_path), ".t.sol", show_internal)
+            .map_err(log_error)?;
 
     // ========================================
     // ======== Generate Specification ========
@@ -210,7 +218,7 @@ fn get_contracts_for_dir<P: AsRef<Path>>(
     dir: P,
     extension: &str,
     show_internal: bool,
-) -> Vec<ParsedContract> {
+) -> Result<Vec<ParsedContract>> {
     let mut contracts: Vec<ParsedContract> = Vec::new();
     for result in WalkDir::new(dir) {
         let dent = match result {
@@ -226,15 +234,17 @@ fn get_contracts_for_dir<P: AsRef<Path>>(
             continue;
         }
 
-        let new_contracts = parse_contracts(file, show_internal);
+        let new_contracts = parse_contracts(file, show_internal)?;
         contracts.extend(new_contracts);
     }
-    contracts
+    Ok(contracts)
 }
 
-fn parse_contracts(file: &Path, show_internal: bool) -> Vec<ParsedContract> {
-    let content = fs::read_to_string(file).unwrap();
-    let (pt, _comments) = crate::parser::parse_solidity(&content, 0).expect("Parsing failed");
+fn parse_contracts(file: &Path, show_internal: bool) -> Result<Vec<ParsedContract>> {
+    let content = fs::read_to_string(file)
+        .map_err(|err| Error::io(format!("Failed to read {}", file.display()), &err))?;
+    let (pt, _comments) = crate::parser::parse_solidity(&content, 0)
+        .map_err(|diagnostics| ParseError { file: file.to_path_buf(), diagnostics })?;
     let mut contracts: Vec<ParsedContract> = Vec::new();
 
     for element in &pt.0 {
@@ -257,7 +267,7 @@ fn parse_contracts(file: &Path, show_internal: bool) -> Vec<ParsedContract> {
             _ => (),
         }
     }
-    contracts
+    Ok(contracts)
 }
 
 fn get_functions_from_contract(
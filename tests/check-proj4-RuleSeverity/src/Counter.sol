@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.17;
+
+contract Counter {
+  error Unauthorized();
+
+  uint256 public constant badConstant = 1;
+
+  uint256 public number;
+
+  function increment(uint256 amount) public {
+    if (msg.sender == address(0)) revert Unauthorized();
+    number += amount;
+  }
+}
@@ -0,0 +1,6 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.17;
+
+contract Legacy {
+  function notPrefixed() internal {}
+}
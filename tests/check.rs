@@ -2,8 +2,16 @@
 /// terminal. Therefore, most testing is done by running the binary against a sample forge
 /// project and checking the output.
 use std::{
-    env,
-    process::{Command, Output},
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    process::{self, Command, Output, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
 };
 
 fn run_scopelint(test_folder: &str) -> Output {
@@ -13,11 +21,80 @@ fn run_scopelint(test_folder: &str) -> Output {
 
     Command::new(binary_path)
         .current_dir(project_path)
-        .arg("check")
+        .args(["check", "--no-snippets", "--no-cache"])
         .output()
         .expect("Failed to execute command")
 }
 
+fn run_scopelint_with_args(test_folder: &str, args: &[&str]) -> Output {
+    let cwd = env::current_dir().unwrap();
+    let project_path = cwd.join("tests").join(test_folder);
+    let binary_path = cwd.join("target/debug/scopelint");
+
+    Command::new(binary_path)
+        .current_dir(project_path)
+        .args(args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+/// A project in a unique temporary directory, with the `foundry.toml` of `check-proj2-NoFindings`.
+/// The directory is removed when the project is dropped, even if the test panics.
+struct TempProject {
+    path: PathBuf,
+}
+
+impl TempProject {
+    /// Creates the project with the given files, by path relative to the project root.
+    fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let path = env::temp_dir().join(format!(
+            "scopelint-test-{name}-{}-{}",
+            process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(path.join("src")).unwrap();
+        fs::copy("tests/check-proj2-NoFindings/foundry.toml", path.join("foundry.toml")).unwrap();
+        let project = Self { path };
+        for (file, content) in files {
+            project.write(file, content);
+        }
+        project
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write(&self, file: &str, content: &str) {
+        let path = self.path.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn read(&self, file: &str) -> String {
+        fs::read_to_string(self.path.join(file)).unwrap()
+    }
+
+    /// Returns a command that runs scopelint in the project.
+    fn command(&self) -> Command {
+        let mut command = Command::new(env::current_dir().unwrap().join("target/debug/scopelint"));
+        command.current_dir(&self.path);
+        command
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        self.command().args(args).output().expect("Failed to execute command")
+    }
+}
+
+impl Drop for TempProject {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
 fn run_scopelint_fix(test_folder: &str) -> Output {
     let cwd = env::current_dir().unwrap();
     let project_path = cwd.join("tests").join(test_folder);
@@ -33,47 +110,68 @@ fn run_scopelint_fix(test_folder: &str) -> Output {
 #[test]
 fn test_check_proj1_all_findings() {
     let output = run_scopelint("check-proj1-AllFindings");
+    assert_eq!(output.status.code(), Some(1));
     let stderr = String::from_utf8(output.stderr).unwrap();
     let findings: Vec<&str> = stderr.split("\n").collect();
 
     let expected_findings = [
-        "Invalid constant or immutable name in ./script/Counter.s.sol on line 7: VERY_bad_constant",
-        "Invalid constant or immutable name in ./script/Counter.s.sol on line 6: bad_constant",
-        "Invalid constant or immutable name in ./script/Counter.s.sol on line 8: sorryBadName",
-        "Invalid constant or immutable name in ./script/ScriptHelpers.sol on line 4: stillNeedGoodNames",
-        "Invalid constant or immutable name in ./src/Counter.sol on line 7: badImmutable",
-        "Invalid constant or immutable name in ./src/Counter.sol on line 8: bad_constant",
-        "Invalid constant or immutable name in ./test/Counter.t.sol on line 7: testVal",
-        "Invalid src method name in ./src/Counter.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/Counter.sol on line 27: internalShouldHaveLeadingUnderscore",
-        "Invalid src method name in ./src/Counter.sol on line 29: privateShouldHaveLeadingUnderscore",
-        "Invalid src method name in ./src/CounterIgnored1.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/CounterIgnored2.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/CounterIgnored3.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/CounterIgnored4.sol on line 1: Missing SPDX-License-Identifier header",
-        "Invalid src method name in ./src/CounterIgnored4.sol on line 29: missingLeadingUnderscoreAndNotIgnored",
-        "Invalid test name in ./test/Counter.t.sol on line 16: testIncrementBadName",
-        "Invalid directive in ./src/Counter.sol: Invalid inline config item: this directive is invalid",
-        "Invalid variable name in ./script/Counter.s.sol on line 25: Local variable 'x' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 19: Parameter 'newNumber' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 34: Parameter 'owner' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 34: Parameter 'spender' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 34: Parameter 'value' should have underscore prefix",
-        "Invalid variable name in ./src/Counter.sol on line 6: State variable '_GOOD__IMMUTABLE_' should NOT have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 20: Parameter 'newNumber' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 41: Parameter 'someImportantData' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 50: Parameter 'someImportantData' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 40: Parameter 'someImportantNumber' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 49: Parameter 'someImportantNumber' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 39: Parameter 'someImportantUser' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 48: Parameter 'someImportantUser' should have underscore prefix",
-        "Invalid variable name in ./src/CounterIgnored3.sol on line 7: State variable '_GOOD__IMMUTABLE_' should NOT have underscore prefix",
-        "Invalid variable name in ./test/Counter.t.sol on line 31: Local variable 'x' should have underscore prefix",
-        "Invalid variable name in ./test/Counter.t.sol on line 21: Parameter 'x' should have underscore prefix",
-        "Invalid error name in ./src/Counter.sol on line 40: Error 'AnotherInvalidError' should be prefixed with 'Counter_'",
-        "Invalid error name in ./src/Counter.sol on line 39: Error 'InvalidError' should be prefixed with 'Counter_'",
-        "Invalid EIP712 typehash in ./src/Counter.sol: EIP712 typehash 'PERMIT_TYPEHASH' parameter mismatch: typehash defines 5 parameters but abi.encode usage uses 3 parameters",
-        "Unused import in ./src/Counter.sol on line 3: Unused import: 'ERC20'",
+        "[SL401] Invalid constant or immutable name in ./script/Counter.s.sol on line 7: VERY_bad_constant",
+        "[SL401] Invalid constant or immutable name in ./script/Counter.s.sol on line 6: bad_constant",
+        "[SL401] Invalid constant or immutable name in ./script/Counter.s.sol on line 8: sorryBadName",
+        "[SL401] Invalid constant or immutable name in ./script/ScriptHelpers.sol on line 4: stillNeedGoodNames",
+        "[SL401] Invalid constant or immutable name in ./src/Counter.sol on line 7: badImmutable",
+        "[SL401] Invalid constant or immutable name in ./src/Counter.sol on line 8: bad_constant",
+        "[SL401] Invalid constant or immutable name in ./test/Counter.t.sol on line 7: testVal",
+        "[SL102] Invalid src method name in ./src/Counter.sol on line 1: Missing SPDX-License-Identifier header",
+        "[SL101] Invalid src method name in ./src/Counter.sol on line 27: internalShouldHaveLeadingUnderscore",
+        "[SL101] Invalid src method name in ./src/Counter.sol on line 29: privateShouldHaveLeadingUnderscore",
+        "[SL102] Invalid src method name in ./src/CounterIgnored1.sol on line 1: Missing SPDX-License-Identifier header",
+        "[SL102] Invalid src method name in ./src/CounterIgnored2.sol on line 1: Missing SPDX-License-Identifier header",
+        "[SL102] Invalid src method name in ./src/CounterIgnored3.sol on line 1: Missing SPDX-License-Identifier header",
+        "[SL102] Invalid src method name in ./src/CounterIgnored4.sol on line 1: Missing SPDX-License-Identifier header",
+        "[SL101] Invalid src method name in ./src/CounterIgnored4.sol on line 29: missingLeadingUnderscoreAndNotIgnored",
+        "[SL201] Invalid test name in ./test/Counter.t.sol on line 16: testIncrementBadName",
+        "[SL901] Invalid directive in ./src/Counter.sol: Invalid inline config item: this directive is invalid",
+        "[SL504] Invalid variable name in ./script/Counter.s.sol on line 25: Local variable 'x' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/Counter.sol on line 19: Parameter 'newNumber' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/Counter.sol on line 34: Parameter 'owner' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/Counter.sol on line 34: Parameter 'spender' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/Counter.sol on line 34: Parameter 'value' should have underscore prefix",
+        "[SL503] Invalid variable name in ./src/Counter.sol on line 6: State variable '_GOOD__IMMUTABLE_' should NOT have underscore prefix",
+        "[SL501] Invalid variable name in ./src/CounterIgnored3.sol on line 20: Parameter 'newNumber' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/CounterIgnored3.sol on line 41: Parameter 'someImportantData' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/CounterIgnored3.sol on line 50: Parameter 'someImportantData' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/CounterIgnored3.sol on line 40: Parameter 'someImportantNumber' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/CounterIgnored3.sol on line 49: Parameter 'someImportantNumber' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/CounterIgnored3.sol on line 39: Parameter 'someImportantUser' should have underscore prefix",
+        "[SL501] Invalid variable name in ./src/CounterIgnored3.sol on line 48: Parameter 'someImportantUser' should have underscore prefix",
+        "[SL503] Invalid variable name in ./src/CounterIgnored3.sol on line 7: State variable '_GOOD__IMMUTABLE_' should NOT have underscore prefix",
+        "[SL504] Invalid variable name in ./test/Counter.t.sol on line 31: Local variable 'x' should have underscore prefix",
+        "[SL501] Invalid variable name in ./test/Counter.t.sol on line 21: Parameter 'x' should have underscore prefix",
+        "[SL601] Invalid error name in ./src/Counter.sol on line 40: Error 'AnotherInvalidError' should be prefixed with 'Counter_'",
+        "[SL601] Invalid error name in ./src/Counter.sol on line 39: Error 'InvalidError' should be prefixed with 'Counter_'",
+        "[SL701] Invalid EIP712 typehash in ./src/Counter.sol: EIP712 typehash 'PERMIT_TYPEHASH' parameter mismatch: typehash defines 5 parameters but abi.encode usage uses 3 parameters",
+        "[SL801] Unused import in ./src/Counter.sol on line 3: Unused import: 'ERC20'",
+        "Summary: 37 finding(s), 10 file(s) scanned, 0 skipped",
+        "  By rule:",
+        "    constant   7",
+        "    src        8",
+        "    test       1",
+        "    directive  1",
+        "    variable   16",
+        "    error      2",
+        "    eip712     1",
+        "    import     1",
+        "  By file:",
+        "    ./script/Counter.s.sol      4",
+        "    ./script/ScriptHelpers.sol  1",
+        "    ./src/Counter.sol           15",
+        "    ./src/CounterIgnored1.sol   1",
+        "    ./src/CounterIgnored2.sol   1",
+        "    ./src/CounterIgnored3.sol   9",
+        "    ./src/CounterIgnored4.sol   2",
+        "    ./test/Counter.t.sol        4",
+        "  Suppressed: 26 by inline directives, 0 by .scopelint overrides",
         "error: Convention checks failed, see details above",
         "error: Formatting validation failed, run `scopelint fmt` to fix",
         "",
@@ -85,6 +183,143 @@ fn test_check_proj1_all_findings() {
     assert_eq!(findings.len(), expected_findings.len());
 }
 
+#[test]
+fn test_check_proj1_baseline() {
+    let baseline_path = env::temp_dir().join("scopelint-test-proj1-baseline.json");
+    let baseline_arg = baseline_path.to_str().unwrap();
+
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--no-cache", "--no-snippets", "--write-baseline", baseline_arg],
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(&format!("info: Wrote 37 finding(s) to baseline {baseline_arg}")));
+
+    // All findings are in the baseline, so only formatting fails.
+    let args = ["check", "--no-cache", "--no-snippets", "--baseline", baseline_arg];
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr, "error: Formatting validation failed, run `scopelint fmt` to fix\n");
+
+    // Drop a finding from the baseline and record one that no longer exists.
+    let mut baseline: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&baseline_path).unwrap()).unwrap();
+    let findings = baseline["findings"].as_array_mut().unwrap();
+    let dropped = findings.remove(0);
+    findings.push(serde_json::json!({
+        "file": "./src/Removed.sol",
+        "rule": "src",
+        "fingerprint": "0000000000000000",
+        "message": "removedFunction",
+    }));
+    fs::write(&baseline_path, baseline.to_string()).unwrap();
+
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &args);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let lines: Vec<&str> = stderr.lines().collect();
+    fs::remove_file(&baseline_path).unwrap();
+
+    assert_eq!(dropped["message"], "VERY_bad_constant");
+    assert_eq!(
+        lines,
+        [
+            "[SL401] Invalid constant or immutable name in ./script/Counter.s.sol on line 7: VERY_bad_constant",
+            "info: 1 baseline finding(s) have been fixed, run with `--write-baseline` to update the baseline",
+            "  ./src/Removed.sol (src): removedFunction",
+            "Summary: 1 finding(s), 10 file(s) scanned, 0 skipped",
+            "  By rule:",
+            "    constant  1",
+            "  By file:",
+            "    ./script/Counter.s.sol  1",
+            "  Suppressed: 26 by inline directives, 0 by .scopelint overrides",
+            "  Baselined: 36",
+            "error: Convention checks failed, see details above",
+            "error: Formatting validation failed, run `scopelint fmt` to fix",
+        ]
+    );
+}
+
+#[test]
+fn test_check_changed_since() {
+    let run_git = |dir: &Path, args: &[&str]| {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=scopelint", "-c", "user.email=scopelint@example.com"])
+            .args(args)
+            .output()
+            .expect("Failed to execute git")
+            .status;
+        assert!(status.success());
+    };
+    let contract = |constants: &str| {
+        format!(
+            "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Counter {{\n{constants}}}\n"
+        )
+    };
+
+    // Commit a contract with one finding, then add a second finding.
+    let project = TempProject::new(
+        "changed-since",
+        &[("src/Counter.sol", &contract("  uint256 public constant badConstant = 1;\n"))],
+    );
+    run_git(project.path(), &["init", "--quiet"]);
+    run_git(project.path(), &["add", "."]);
+    run_git(project.path(), &["commit", "--quiet", "--message", "Initial commit"]);
+    project.write(
+        "src/Counter.sol",
+        &contract(
+            "  uint256 public constant badConstant = 1;\n  uint256 public constant otherBadConstant = 2;\n",
+        ),
+    );
+
+    let run_check = |args: &[&str]| {
+        let output = project
+            .command()
+            .args(["check", "--no-snippets", "--changed-since", "HEAD"])
+            .args(args)
+            .output()
+            .expect("Failed to execute command");
+        String::from_utf8(output.stderr).unwrap()
+    };
+    let changed_lines = run_check(&[]);
+    let changed_files = run_check(&["--whole-files"]);
+
+    assert_eq!(
+        changed_lines.lines().collect::<Vec<_>>(),
+        [
+            "[SL401] Invalid constant or immutable name in ./src/Counter.sol on line 6: otherBadConstant",
+            "Summary: 1 finding(s), 1 file(s) scanned, 0 skipped",
+            "  By rule:",
+            "    constant  1",
+            "  By file:",
+            "    ./src/Counter.sol  1",
+            "  Suppressed: 0 by inline directives, 0 by .scopelint overrides",
+            "error: Convention checks failed, see details above",
+        ]
+    );
+    assert_eq!(
+        changed_files.lines().collect::<Vec<_>>(),
+        [
+            "[SL401] Invalid constant or immutable name in ./src/Counter.sol on line 5: badConstant",
+            "[SL401] Invalid constant or immutable name in ./src/Counter.sol on line 6: otherBadConstant",
+            "Summary: 2 finding(s), 1 file(s) scanned, 0 skipped",
+            "  By rule:",
+            "    constant  2",
+            "  By file:",
+            "    ./src/Counter.sol  2",
+            "  Suppressed: 0 by inline directives, 0 by .scopelint overrides",
+            "error: Convention checks failed, see details above",
+        ]
+    );
+
+    // The diff prefixes in the user's git config are overridden.
+    for config in ["diff.noprefix", "diff.mnemonicPrefix"] {
+        run_git(project.path(), &["config", config, "true"]);
+        assert_eq!(run_check(&[]), changed_lines, "{config}");
+        run_git(project.path(), &["config", "--unset", config]);
+    }
+}
+
 #[test]
 fn test_check_proj2_no_findings() {
     let output = run_scopelint("check-proj2-NoFindings");
@@ -99,6 +334,199 @@ fn test_check_proj2_no_findings() {
     assert_eq!(findings.len(), expected_findings.len());
 }
 
+#[test]
+fn test_check_proj4_rule_severities() {
+    let output = run_scopelint("check-proj4-RuleSeverity");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let findings: Vec<&str> = stderr.split("\n").collect();
+
+    // The `error` rule is set to `warn`, the `constant` rule is `off`, the `variable` rule is
+    // ignored in `Counter.sol` and `Legacy.sol` is ignored entirely.
+    let expected_findings = [
+        "warning: [SL601] Invalid error name in ./src/Counter.sol on line 5: Error 'Unauthorized' should be prefixed with 'Counter_'",
+        "Summary: 1 finding(s), 1 file(s) scanned, 1 skipped",
+        "  By rule:",
+        "    error  1",
+        "  By file:",
+        "    ./src/Counter.sol  1",
+        "  Suppressed: 0 by inline directives, 1 by .scopelint overrides",
+        "warning: Convention checks passed with 1 warning(s), see details above",
+        "",
+    ];
+
+    for (i, expected) in expected_findings.iter().enumerate() {
+        assert_eq!(findings[i], *expected);
+    }
+    assert_eq!(findings.len(), expected_findings.len());
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_check_proj4_json_severities() {
+    let output = run_scopelint_with_args(
+        "check-proj4-RuleSeverity",
+        &["check", "--no-cache", "--format", "json"],
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let findings: Vec<_> =
+        json["findings"].as_array().unwrap().iter().filter(|f| f["is_ignored"] == false).collect();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0]["kind"], "error");
+    assert_eq!(findings[0]["severity"], "warning");
+    assert_eq!(json["summary"]["failed"], 0);
+    assert_eq!(json["summary"]["warnings"], 1);
+    assert_eq!(json["summary"]["valid"], true);
+
+    assert_eq!(json["summary"]["by_kind"], serde_json::json!({ "error": 1 }));
+    assert_eq!(json["summary"]["by_file"], serde_json::json!({ "./src/Counter.sol": 1 }));
+    assert_eq!(json["summary"]["suppressed_inline"], 0);
+    assert_eq!(json["summary"]["suppressed_config"], 1);
+    assert_eq!(json["summary"]["files_scanned"], 1);
+    assert_eq!(json["summary"]["files_skipped"], 1);
+}
+
+#[test]
+fn test_check_proj1_snippets() {
+    let output = run_scopelint_with_args("check-proj1-AllFindings", &["check", "--no-cache"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    let expected_snippet = [
+        "[SL201] Invalid test name in ./test/Counter.t.sol on line 16: testIncrementBadName",
+        "  --> ./test/Counter.t.sol:16:12",
+        "   |",
+        "16 |   function testIncrementBadName() public {",
+        "   |            ^^^^^^^^^^^^^^^^^^^^",
+    ]
+    .join("\n");
+    assert!(stderr.contains(&expected_snippet), "stderr:\n{stderr}");
+
+    // File-level findings point at the start of the file.
+    let expected_snippet = [
+        "[SL102] Invalid src method name in ./src/Counter.sol on line 1: Missing SPDX-License-Identifier header",
+        " --> ./src/Counter.sol:1:1",
+        "  |",
+        "1 | pragma solidity ^0.8.17;",
+        "  | ^",
+    ]
+    .join("\n");
+    assert!(stderr.contains(&expected_snippet), "stderr:\n{stderr}");
+}
+
+#[test]
+fn test_check_proj1_json_format() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--no-cache", "--format", "json"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is valid JSON");
+
+    let findings = json["findings"].as_array().unwrap();
+    let active: Vec<&serde_json::Value> = findings
+        .iter()
+        .filter(|f| !f["is_disabled"].as_bool().unwrap() && !f["is_ignored"].as_bool().unwrap())
+        .collect();
+    assert_eq!(active.len(), 37);
+    assert_eq!(json["summary"]["failed"], 37);
+    assert_eq!(json["summary"]["total"], findings.len());
+    assert_eq!(json["summary"]["valid"], false);
+
+    assert!(active.iter().any(|f| f["kind"] == "test" &&
+        f["file"] == "./test/Counter.t.sol" &&
+        f["line"] == 16 &&
+        f["column"] == 12 &&
+        f["end_line"] == 16 &&
+        f["end_column"] == 32 &&
+        f["message"] == "testIncrementBadName"));
+}
+
+#[test]
+fn test_check_proj2_json_format() {
+    let output = run_scopelint_with_args(
+        "check-proj2-NoFindings",
+        &["check", "--no-cache", "--format", "json"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is valid JSON");
+
+    assert_eq!(json["findings"].as_array().unwrap().len(), 0);
+    assert_eq!(json["summary"]["valid"], true);
+}
+
+#[test]
+fn test_check_proj1_sarif_format() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--no-cache", "--format", "sarif"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let sarif: serde_json::Value = serde_json::from_str(&stdout).expect("stdout is valid SARIF");
+
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert!(rules.iter().any(|r| r["id"] == "SL201" && r["properties"]["tags"][0] == "test"));
+
+    let results = run["results"].as_array().unwrap();
+    let (suppressed, active): (Vec<_>, Vec<_>) =
+        results.iter().partition(|r| r.get("suppressions").is_some());
+    assert_eq!(active.len(), 37);
+    assert!(!suppressed.is_empty());
+    assert!(suppressed.iter().all(|r| r["suppressions"][0]["kind"] == "inSource"));
+
+    let test_result = active.iter().find(|r| r["ruleId"] == "SL201").unwrap();
+    let location = &test_result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "test/Counter.t.sol");
+    assert_eq!(location["region"]["startLine"], 16);
+    assert_eq!(location["region"]["startColumn"], 12);
+    assert_eq!(location["region"]["endLine"], 16);
+    assert_eq!(location["region"]["endColumn"], 32);
+    assert_eq!(rules[test_result["ruleIndex"].as_u64().unwrap() as usize]["id"], "SL201");
+}
+
+#[test]
+fn test_check_proj1_github_format() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--no-cache", "--format", "github"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // Formatting annotations are reported per file, without a line number.
+    let annotations: Vec<&str> =
+        stdout.lines().filter(|l| l.starts_with("::error ") && l.contains(",line=")).collect();
+
+    assert_eq!(annotations.len(), 37);
+    assert!(stdout.lines().any(|l| l.contains("title=scopelint (fmt)")));
+    assert!(annotations.contains(
+        &"::error file=test/Counter.t.sol,line=16,endLine=16,col=12,endColumn=32,title=scopelint (test)::[SL201] Invalid test name in ./test/Counter.t.sol on line 16: testIncrementBadName"
+    ));
+}
+
+#[test]
+fn test_check_proj1_junit_format() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--no-cache", "--format", "junit"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(stdout.contains(r#"<testsuites name="scopelint" tests="66" failures="37""#));
+    assert_eq!(stdout.matches("<testsuite ").count(), 11);
+    assert_eq!(stdout.matches("<failure ").count(), 37);
+    assert!(stdout.contains(
+        r#"<testsuite name="scopelint.test" tests="1" failures="1" errors="0" skipped="0">"#
+    ));
+    assert!(stdout.contains(
+        r#"<testcase classname="scopelint.test" name="./test/Counter.t.sol:16: testIncrementBadName">"#
+    ));
+    // Error messages contain quotes, which must be escaped.
+    assert!(stdout.contains(
+        "message=\"Error &apos;InvalidError&apos; should be prefixed with &apos;Counter_&apos;\""
+    ));
+}
+
 /// Projects with contracts/ instead of src/ must not hit "No such file or directory" for ./src.
 /// This project has [profile.default] src = "contracts" and no src/ directory.
 #[test]
@@ -135,3 +563,437 @@ fn test_fix_removes_unused_import() {
         "Fixed file should not contain unused import IERC20; content:\n{content}"
     );
 }
+
+#[test]
+fn test_unused_directives() {
+    let project = TempProject::new(
+        "unused-directives",
+        &[(
+            "src/Counter.sol",
+            "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Counter {\n  \
+             // scopelint: ignore-constant-next-line\n  uint256 public constant badConstant = 1;\n  \
+             // scopelint: ignore-constant-next-line\n  uint256 public constant GOOD_CONSTANT = 2;\n  \
+             uint256 public number; // scopelint: ignore-line\n}\n",
+        )],
+    );
+
+    let output = project.run(&["check", "--no-snippets"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains(
+        "[SL902] Unused directive in ./src/Counter.sol on line 7: \
+         // scopelint: ignore-constant-next-line\n"
+    ));
+    assert!(stderr.contains(
+        "[SL902] Unused directive in ./src/Counter.sol on line 9: // scopelint: ignore-line\n"
+    ));
+    assert!(stderr.contains("    unused-directive  2\n"));
+
+    let output = project.run(&["fix"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.starts_with("info: Fixed unused directive findings in 1 file(s)\n"));
+    assert_eq!(
+        project.read("src/Counter.sol"),
+        "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Counter {\n  \
+         // scopelint: ignore-constant-next-line\n  uint256 public constant badConstant = 1;\n  \
+         uint256 public constant GOOD_CONSTANT = 2;\n  uint256 public number;\n}\n"
+    );
+}
+
+#[test]
+fn test_ignore_reasons() {
+    let project = TempProject::new(
+        "ignore-reasons",
+        &[(
+            "src/Counter.sol",
+            "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Counter {\n  \
+             // scopelint: ignore-constant-next-line -- matches the deployed ABI\n  \
+             uint256 public constant badConstant = 1;\n  \
+             // scopelint: ignore-constant-next-line\n  uint256 public constant otherBad = 2;\n}\n",
+        )],
+    );
+    let run_check = |args: &[&str]| {
+        project.command().arg("check").args(args).output().expect("Failed to execute command")
+    };
+
+    // Reasons are optional by default, and kept on the suppressed findings.
+    let output = run_check(&["--format", "json"]);
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let reasons: Vec<&serde_json::Value> =
+        report["findings"].as_array().unwrap().iter().map(|f| &f["ignore_reason"]).collect();
+    assert_eq!(reasons, [&serde_json::json!("matches the deployed ABI"), &serde_json::Value::Null]);
+
+    // With `require_reason`, directives without a reason are reported.
+    project.write(".scopelint", "[ignore]\nrequire_reason = true\n");
+    let output = run_check(&["--no-snippets"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.starts_with(
+        "[SL903] Invalid directive in ./src/Counter.sol: Missing reason for ignore directive: \
+         // scopelint: ignore-constant-next-line\n"
+    ));
+    assert!(stderr.contains("Summary: 1 finding(s), 1 file(s) scanned, 0 skipped\n"));
+}
+
+#[test]
+fn test_explain_rule() {
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["explain", "sl201"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("SL201 test-name (test)\n"));
+    assert!(stdout.contains("    function testIncrementWorks() public {}"));
+    assert!(stdout.contains("    // scopelint: ignore-test-next-line"));
+
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["explain", "SL000"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        stderr,
+        "error: Unknown rule code 'SL000', run `scopelint explain` to list all rules\n"
+    );
+}
+
+#[test]
+fn test_cache() {
+    let project = TempProject::new(
+        "cache",
+        &[(
+            "src/Counter.sol",
+            "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Counter {\n  \
+             uint256 public constant badConstant = 1;\n}\n",
+        )],
+    );
+    let cache_path = project.path().join(".scopelint-cache/findings.json");
+
+    let run = |args: &[&str]| String::from_utf8(project.run(args).stderr).unwrap();
+    let finding = |name: &str| {
+        format!("[SL401] Invalid constant or immutable name in ./src/Counter.sol on line 5: {name}")
+    };
+
+    // The first run caches the findings.
+    assert!(run(&["check", "--no-snippets"]).contains(&finding("badConstant")));
+    let cached = fs::read_to_string(&cache_path).unwrap();
+    assert!(cached.contains("badConstant"));
+
+    // Unchanged files reuse the cached findings, unless the cache is disabled.
+    fs::write(&cache_path, cached.replace("badConstant", "cachedConstant")).unwrap();
+    assert!(run(&["check", "--no-snippets"]).contains(&finding("cachedConstant")));
+    assert!(run(&["check", "--no-snippets", "--no-cache"]).contains(&finding("badConstant")));
+
+    // Changing `.scopelint` invalidates the cache.
+    project.write(".scopelint", "[rules]\nconstant = \"warn\"\n");
+    let stderr = run(&["check", "--no-snippets"]);
+    assert!(stderr.contains(&finding("badConstant")), "{stderr}");
+    assert!(stderr.contains("Convention checks passed with 1 warning(s)"));
+}
+
+#[test]
+fn test_invalid_config() {
+    let project =
+        TempProject::new("invalid-config", &[(".scopelint", "[rules]\nconstnt = \"warn\"\n")]);
+
+    for args in [&["check", "--no-cache"][..], &["fix"]] {
+        let output = project.run(args);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(stderr.starts_with("error: Failed to parse "), "{stderr}");
+        assert!(stderr.contains(".scopelint: "), "{stderr}");
+    }
+
+    // `spec` only reads `foundry.toml`.
+    project.write(".scopelint", "");
+    project.write("foundry.toml", "[profile.default\n");
+    for args in [&["check", "--no-cache"][..], &["fix"], &["spec"]] {
+        let output = project.run(args);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(stderr.contains("foundry.toml: "), "{stderr}");
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    let project = TempProject::new(
+        "parse-errors",
+        &[
+            (
+                "src/Broken.sol",
+                "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Broken {\n  \
+                 function increment() public {\n}\n",
+            ),
+            (
+                "src/Counter.sol",
+                "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Counter {\n  \
+                 uint256 public constant badConstant = 1;\n}\n",
+            ),
+        ],
+    );
+    let run = || project.run(&["check", "--no-snippets", "--no-cache"]);
+
+    // The file that cannot be parsed is reported, and the other file is still checked.
+    let output = run();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success());
+    assert!(stderr.contains("[SL001] Failed to parse ./src/Broken.sol on line 6: "), "{stderr}");
+    assert!(stderr.contains(
+        "[SL401] Invalid constant or immutable name in ./src/Counter.sol on line 5: badConstant"
+    ));
+
+    // Parse errors can be turned off in `.scopelint`.
+    project.write(".scopelint", "[rules]\nparse = \"off\"\n");
+    let stderr = String::from_utf8(run().stderr).unwrap();
+    assert!(!stderr.contains("[SL001]"), "{stderr}");
+    assert!(stderr.contains("badConstant"));
+}
+
+#[test]
+fn test_check_paths() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--no-cache", "--no-snippets", "src/Counter.sol", "test/*.t.sol"],
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Summary: 19 finding(s), 2 file(s) scanned, 0 skipped\n"), "{stderr}");
+    assert!(stderr.contains("[SL201] Invalid test name in ./test/Counter.t.sol on line 16"));
+    assert!(!stderr.contains("./script/"));
+
+    // Files ignored in `.scopelint` are skipped even when given explicitly.
+    let output = run_scopelint_with_args(
+        "check-proj4-RuleSeverity",
+        &["check", "--no-cache", "--format", "json", "./src/Legacy.sol"],
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["files_scanned"], 0);
+    assert_eq!(json["summary"]["files_skipped"], 1);
+
+    let output =
+        run_scopelint_with_args("check-proj2-NoFindings", &["check", "--no-cache", "lib/*.sol"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.starts_with("error: No Solidity files match 'lib/*.sol'\n"), "{stderr}");
+
+    // Solidity files outside the `src`, `script` and `test` directories are not checked.
+    let project = TempProject::new(
+        "paths",
+        &[("src/Counter.sol", "contract Counter {}"), ("lib/Dep.sol", "contract Dep {}")],
+    );
+    let output = project.run(&["check", "--no-cache", "lib/Dep.sol"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.starts_with("error: 'lib/Dep.sol' is not in the src, script or test directories\n"),
+        "{stderr}"
+    );
+    let output = project.run(&["check", "--no-cache", "--format", "json", "."]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["files_scanned"], 1);
+}
+
+#[test]
+fn test_check_rule_filters() {
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--no-cache", "--no-snippets", "--only", "test", "--only", "fmt"],
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with(
+        "[SL201] Invalid test name in ./test/Counter.t.sol on line 16: testIncrementBadName\n\
+         Summary: 1 finding(s), 10 file(s) scanned, 0 skipped\n"
+    ));
+    assert!(stderr.contains("error: Formatting validation failed"));
+
+    let output = run_scopelint_with_args(
+        "check-proj1-AllFindings",
+        &["check", "--no-cache", "--format", "json", "--skip", "fmt", "--skip", "variable"],
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let findings = json["findings"].as_array().unwrap();
+    assert!(!findings.is_empty());
+    assert!(findings.iter().all(|finding| finding["kind"] != "variable"));
+    assert!(!String::from_utf8(output.stderr).unwrap().contains("Formatting validation failed"));
+
+    let output = run_scopelint_with_args("check-proj2-NoFindings", &["check", "--only", "tests"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("invalid value 'tests' for '--only <RULE>': unknown rule"), "{stderr}");
+}
+
+#[test]
+fn test_root_and_config() {
+    let cwd = env::current_dir().unwrap();
+    let shared = TempProject::new("root", &[("shared.scopelint", "[rules]\nerror = \"off\"\n")]);
+    let config_path = shared.path().join("shared.scopelint");
+    // Runs scopelint outside of any project, so only `--root` and `--config` locate it.
+    let run = |args: &[&str]| {
+        Command::new(cwd.join("target/debug/scopelint"))
+            .current_dir(env::temp_dir())
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    };
+    let proj1 = cwd.join("tests/check-proj1-AllFindings");
+    let proj4 = cwd.join("tests/check-proj4-RuleSeverity");
+
+    // Paths are relative to the root, and `forge fmt` runs in it.
+    let output =
+        run(&["check", "--root", proj1.to_str().unwrap(), "--no-cache", "src/Counter.sol"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.contains("in ./src/Counter.sol on line"), "{stderr}");
+    assert!(stderr.contains("error: Formatting validation failed"), "{stderr}");
+
+    let output =
+        run(&["--root", proj4.to_str().unwrap(), "check", "--no-cache", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["files_skipped"], 1);
+    let findings = json["findings"].as_array().unwrap();
+    assert!(findings
+        .iter()
+        .any(|finding| finding["kind"] == "error" && finding["severity"] == "warning"));
+
+    // The given `.scopelint` replaces the one in the project.
+    let output = run(&[
+        "--root",
+        proj4.to_str().unwrap(),
+        "--config",
+        config_path.to_str().unwrap(),
+        "check",
+        "--no-cache",
+        "--format",
+        "json",
+    ]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["files_skipped"], 0);
+    assert!(json["findings"].as_array().unwrap().iter().all(|finding| finding["kind"] != "error"));
+
+    // Paths other than the root are relative to the root, not to the current directory.
+    let project = TempProject::new(
+        "relative-paths",
+        &[
+            (
+                "src/Counter.sol",
+                "contract Counter {\n  uint256 public constant badConstant = 1;\n}\n",
+            ),
+            ("configs/shared.scopelint", "[rules]\nsrc = \"off\"\n"),
+        ],
+    );
+    let root = project.path().to_str().unwrap();
+    let args = ["--root", root, "--config", "configs/shared.scopelint", "check", "--no-cache"];
+    let output = run(&[&args[..], &["--format", "json"]].concat());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0]["kind"], "constant");
+    run(&[&args[..], &["--write-baseline", "baseline.json"]].concat());
+    assert!(project.path().join("baseline.json").is_file());
+    let output = run(&[&args[..], &["--baseline", "baseline.json"]].concat());
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let output = run(&["--root", root, "--config", "missing.scopelint", "check"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.starts_with("error: Config file missing.scopelint does not exist\n"),
+        "{stderr}"
+    );
+
+    let output = run(&["check", "--root", "scopelint-test-missing-root"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("for '--root <DIR>': not a directory"), "{stderr}");
+}
+
+#[test]
+fn test_check_stdin() {
+    let src = "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Foo {\n  \
+               uint256 public constant badConstant = 1;\n  function bar() internal {}\n}\n";
+    let run = |filename: &str| {
+        let cwd = env::current_dir().unwrap();
+        let mut child = Command::new(cwd.join("target/debug/scopelint"))
+            .current_dir(cwd.join("tests/check-proj2-NoFindings"))
+            .args(["check", "--format", "json", "--stdin", "--stdin-filename", filename])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to execute command");
+        child.stdin.take().unwrap().write_all(src.as_bytes()).unwrap();
+        let output = child.wait_with_output().unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let codes: Vec<String> = json["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|finding| format!("{} {}", finding["code"], finding["file"]))
+            .collect();
+        (output.status.success(), codes)
+    };
+
+    // The filename decides which rules apply, the file itself does not need to exist.
+    let (success, codes) = run("src/Foo.sol");
+    assert!(!success);
+    assert_eq!(codes, [r#""SL401" "./src/Foo.sol""#, r#""SL101" "./src/Foo.sol""#]);
+
+    let (success, codes) = run("test/Foo.t.sol");
+    assert!(!success);
+    assert_eq!(codes, [r#""SL401" "./test/Foo.t.sol""#]);
+}
+
+#[test]
+fn test_check_watch() {
+    let counter =
+        "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.17;\n\ncontract Counter {\n  \
+                   uint256 public constant GOOD_CONSTANT = 1;\n}\n";
+    let project = TempProject::new("watch", &[("src/Counter.sol", counter)]);
+
+    let mut child = project
+        .command()
+        .args(["check", "--no-snippets", "--watch"])
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    let (sender, receiver) = mpsc::channel();
+    let stderr = child.stderr.take().unwrap();
+    thread::spawn(move || {
+        for line in BufReader::new(stderr).lines() {
+            if sender.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    // Returns the lines printed until the check has run and the next changes are awaited.
+    let next_run = || -> Vec<String> {
+        let mut lines = Vec::new();
+        loop {
+            let line = receiver.recv_timeout(Duration::from_secs(30)).expect("Check did not run");
+            if line.starts_with("info: Watching for changes") {
+                return lines;
+            }
+            lines.push(line);
+        }
+    };
+
+    let first_run = next_run();
+    project.write("src/Counter.sol", &counter.replace("GOOD_CONSTANT", "badConstant"));
+    let second_run = next_run();
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(first_run.iter().all(|line| !line.contains("[SL401]")), "{first_run:?}");
+    assert!(second_run.contains(
+        &"[SL401] Invalid constant or immutable name in ./src/Counter.sol on line 5: badConstant"
+            .to_string()
+    ));
+
+    // Machine-readable documents can't be printed on every change.
+    let output = project.run(&["check", "--watch", "--format", "json"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("the argument '--watch' cannot be used with '--format json'"),
+        "{stderr}"
+    );
+}